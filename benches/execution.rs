@@ -0,0 +1,104 @@
+//! Measures execution speed of both execution modes, and heap allocations made while running,
+//! which should be none.
+//!
+//! Run with `cargo bench --bench execution`.
+//!
+//! Cycle execution, 20M cycles, best of 5, before and after tasks were made enums dispatched
+//! statically instead of boxed trait objects:
+//!   before: ~0.74s (~27 MHz), 8078721 allocations
+//!   after:  ~0.52s (~38 MHz), 0 allocations
+
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  hint::black_box,
+  sync::atomic::{AtomicUsize, Ordering},
+  time::Instant,
+};
+
+use cpu6502::{
+  cpu::{CPU, ExecutionMode},
+  memory::Generic64kMem,
+};
+
+const CYCLES: usize = 20_000_000;
+const RUNS: usize = 5;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Endless loop mixing the common addressing modes, stack operations and branches.
+fn program() -> Generic64kMem {
+  let mut memory = Generic64kMem::new();
+  memory.insert(
+    0x0600,
+    &[
+      0xA2, 0x00, // LDX #$00
+      0xA0, 0x00, // LDY #$00
+      0xB1, 0x80, // loop: LDA ($80),Y
+      0x69, 0x01, // ADC #$01
+      0x9D, 0x00, 0x03, // STA $0300,X
+      0xEE, 0x00, 0x02, // INC $0200
+      0x20, 0x20, 0x06, // JSR $0620
+      0xE8, // INX
+      0xC8, // INY
+      0xD0, 0xEF, // BNE loop
+      0x4C, 0x04, 0x06, // JMP loop
+    ],
+  );
+  memory.insert(
+    0x0620,
+    &[
+      0x48, // PHA
+      0x06, 0x10, // ASL $10
+      0xC5, 0x10, // CMP $10
+      0x68, // PLA
+      0x60, // RTS
+    ],
+  );
+  memory.insert(0x0080, &[0x00, 0x04]);
+  memory.insert(0xFFFC, &[0x00, 0x06]);
+  memory
+}
+
+fn main() {
+  for mode in [ExecutionMode::Cycle, ExecutionMode::Instruction] {
+    bench(mode);
+  }
+}
+
+fn bench(mode: ExecutionMode) {
+  let mut best = f64::MAX;
+  let mut allocations = 0;
+  for _ in 0..RUNS {
+    let mut memory = program();
+    let mut cpu = CPU::new_nmos();
+    cpu.reset(&memory);
+    cpu.set_execution_mode(mode);
+
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    black_box(cpu.run_for_cycles(&mut memory, CYCLES));
+    best = best.min(start.elapsed().as_secs_f64());
+    allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+  }
+
+  println!(
+    "{mode:?} execution: {CYCLES} cycles in {best:.3}s ({:.2} MHz), {allocations} allocations",
+    CYCLES as f64 / best / 1_000_000.0
+  );
+}
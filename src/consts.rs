@@ -3,7 +3,9 @@ pub type Word = u16;
 
 pub const STACK_PAGE_HI: Word = 0x0100;
 
+pub const NMI_INTERRUPT_VECTOR: Word = 0xFFFA;
 pub const BRK_INTERRUPT_VECTOR: Word = 0xFFFE;
 pub const RESET_VECTOR: Word = 0xFFFC;
 
 pub const DEFAULT_INSTRUCTION_HISTORY_CAPACITY: usize = 32;
+pub const DEFAULT_BUS_ACCESS_CAPACITY: usize = 1024;
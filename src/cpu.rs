@@ -1,9 +1,11 @@
+use strum::FromRepr;
+
 use addressing::AddressingMode;
 use tasks::Tasks;
-use tasks::read_memory::{AddressingReadMemoryTasks, ReadMemoryTasks};
+use tasks::read_memory::AddressingReadMemoryTasks;
 
 use super::consts::{Byte, Word};
-use crate::consts::RESET_VECTOR;
+use crate::consts::{BRK_INTERRUPT_VECTOR, NMI_INTERRUPT_VECTOR, RESET_VECTOR};
 use crate::cpu::addressing::absolute::{
   AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks, AccessVariant,
 };
@@ -12,25 +14,48 @@ use crate::cpu::addressing::indirect::{
   IndexIndirectXAddressingTasks, IndirectAddressingTasks, IndirectIndexYAddressingTasks,
 };
 use crate::cpu::addressing::zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks};
-use crate::cpu::addressing::{AddressingTasks, OffsetVariant};
-use crate::cpu::instructions::INSTRUCTIONS;
+use crate::cpu::addressing::{AddressingModeTasks, OffsetVariant};
+use crate::cpu::instructions::{BRK, BrkTasks, INSTRUCTIONS, InstructionTasks, Interrupt};
 use crate::cpu::tasks::read_memory::ImmediateReadMemoryTasks;
+use crate::power_on::Rng;
 use crate::{consts::STACK_PAGE_HI, memory::Memory};
 
 mod addressing;
+pub mod batch;
+pub mod builder;
 pub mod debugger;
 mod instructions;
+mod interpreter;
+pub mod pacer;
 mod processor_status;
+pub mod save_state;
+pub mod scheduler;
 mod tasks;
 pub mod utils;
 
-#[derive(Copy, Clone, PartialEq)]
-enum ChipVariant {
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Copy, Clone, PartialEq, FromRepr)]
+#[repr(u8)]
+pub enum ChipVariant {
   NMOS,
   RockwellCMOS,
   WDCCMOS,
 }
 
+/// How `step` and the `run_*` methods execute instructions. `tick` always advances one cycle.
+#[derive(Debug, Copy, Clone, Default, PartialEq, FromRepr)]
+#[repr(u8)]
+pub enum ExecutionMode {
+  /// Every cycle is ticked, with its bus accesses.
+  #[default]
+  Cycle,
+  /// Whole instructions are executed at once and their cycles added up. Dummy reads are skipped.
+  Instruction,
+}
+
+// Longest instruction or interrupt sequence, opcode fetch included.
+const MAX_INSTRUCTION_CYCLES: usize = 7;
+
 #[derive(Copy, Clone, PartialEq)]
 enum Registers {
   StackPointer,
@@ -40,11 +65,44 @@ enum Registers {
   IndexY,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+  /// The instruction being executed completed.
+  InstructionDone,
+  /// The cycle budget was used up, possibly in the middle of an instruction.
+  CycleBudget,
+  /// The predicate was satisfied.
+  Predicate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunResult {
+  pub cycles: usize,
+  pub stop_reason: StopReason,
+}
+
+/// Programmer-visible state of the CPU.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuState {
+  pub pc: Word,
+  pub sp: Byte,
+  pub a: Byte,
+  pub x: Byte,
+  pub y: Byte,
+  pub p: Byte,
+  pub cycle: usize,
+  pub variant: ChipVariant,
+}
+
+/// Cloning includes the progress of the instruction being executed, so the clone continues
+/// exactly like the original when given the same memory.
+#[derive(Clone)]
 pub struct CPU {
   addr: Address,
   chip_variant: ChipVariant,
   current_instruction: Option<InstructionExecution>,
   cycle: usize,
+  execution_mode: ExecutionMode,
   program_counter: Word,
   stack_pointer: Byte,
   accumulator: Byte,
@@ -52,8 +110,21 @@ pub struct CPU {
   index_register_y: Byte,
   processor_status: processor_status::ProcessorStatus,
   sync: bool,
+  irq: bool,
+  nmi: bool,
+  nmi_pending: bool,
+  reset_vector: Word,
+  brk_vector: Word,
+  nmi_vector: Word,
 }
 
+// CPUs are moved to worker threads with their memory and debugger to run emulations in
+// parallel
+const _: () = {
+  const fn assert_send<T: Send>() {}
+  assert_send::<(CPU, debugger::Debugger, crate::memory::Generic64kMem)>();
+};
+
 impl CPU {
   fn new(chip_variant: ChipVariant) -> Self {
     CPU {
@@ -61,6 +132,7 @@ impl CPU {
       chip_variant,
       current_instruction: None,
       cycle: 0,
+      execution_mode: ExecutionMode::default(),
       program_counter: RESET_VECTOR,
       stack_pointer: 0x00,
       accumulator: 0,
@@ -68,6 +140,12 @@ impl CPU {
       index_register_y: 0,
       processor_status: processor_status::ProcessorStatus::default(),
       sync: false,
+      irq: false,
+      nmi: false,
+      nmi_pending: false,
+      reset_vector: RESET_VECTOR,
+      brk_vector: BRK_INTERRUPT_VECTOR,
+      nmi_vector: NMI_INTERRUPT_VECTOR,
     }
   }
 
@@ -83,8 +161,20 @@ impl CPU {
     CPU::new(ChipVariant::WDCCMOS)
   }
 
+  /// Fills A, X, Y, SP and P with values from `rng`, as left by power-on. The break flag is
+  /// kept clear and the unused flag set, as they are not backed by latches.
+  pub fn randomize_registers(&mut self, rng: &mut Rng) {
+    self.accumulator = rng.next_byte();
+    self.index_register_x = rng.next_byte();
+    self.index_register_y = rng.next_byte();
+    self.stack_pointer = rng.next_byte();
+    self
+      .processor_status
+      .set((rng.next_byte() & 0b1100_1111) | 0b0010_0000);
+  }
+
   pub fn reset(&mut self, memory: &dyn Memory) {
-    self.program_counter = self.fetch_address_from(RESET_VECTOR, memory);
+    self.program_counter = self.fetch_address_from(self.reset_vector, memory);
     self.processor_status.change_interrupt_disable_flag(true);
   }
 
@@ -92,6 +182,56 @@ impl CPU {
     self.processor_status.into()
   }
 
+  pub fn state(&self) -> CpuState {
+    CpuState {
+      pc: self.program_counter,
+      sp: self.stack_pointer,
+      a: self.accumulator,
+      x: self.index_register_x,
+      y: self.index_register_y,
+      p: self.processor_status.into(),
+      cycle: self.cycle,
+      variant: self.chip_variant,
+    }
+  }
+
+  /// Overwrites the registers. Meant to be used between instructions, an instruction in progress
+  /// continues with the new values.
+  pub fn set_state(&mut self, state: CpuState) {
+    self.program_counter = state.pc;
+    self.stack_pointer = state.sp;
+    self.accumulator = state.a;
+    self.index_register_x = state.x;
+    self.index_register_y = state.y;
+    self.processor_status.set(state.p);
+    self.cycle = state.cycle;
+    self.chip_variant = state.variant;
+  }
+
+  pub fn execution_mode(&self) -> ExecutionMode {
+    self.execution_mode
+  }
+
+  /// Can be switched at any time, an instruction in progress is finished cycle by cycle.
+  pub fn set_execution_mode(&mut self, execution_mode: ExecutionMode) {
+    self.execution_mode = execution_mode;
+  }
+
+  /// Sets the level of the IRQ line. While asserted, an interrupt is taken before every
+  /// instruction unless interrupts are disabled.
+  pub fn set_irq(&mut self, asserted: bool) {
+    self.irq = asserted;
+  }
+
+  /// Sets the level of the NMI line. Asserting it makes the CPU take an interrupt before the
+  /// next instruction, it has to be released before it can trigger another one.
+  pub fn set_nmi(&mut self, asserted: bool) {
+    if asserted && !self.nmi {
+      self.nmi_pending = true;
+    }
+    self.nmi = asserted;
+  }
+
   pub fn tick(&mut self, memory: &mut dyn Memory) {
     let current_instruction = self.current_instruction.take();
     match current_instruction {
@@ -99,17 +239,20 @@ impl CPU {
         self.sync = false;
         let tasks_done = current_instruction.tasks.tick(self, memory);
         self.cycle += 1;
-        if tasks_done {
-          return;
+        if !tasks_done {
+          self.current_instruction = Some(current_instruction);
         }
-
-        self.current_instruction = Some(current_instruction);
       }
       None => {
         self.sync = true;
-        self.current_instruction = Some(self.schedule_instruction(memory));
+        self.current_instruction = Some(match self.take_interrupt() {
+          Some(interrupt) => self.schedule_interrupt(interrupt, memory),
+          None => self.schedule_instruction(memory),
+        });
       }
     }
+
+    memory.end_cycle();
   }
 
   #[inline]
@@ -117,6 +260,78 @@ impl CPU {
     self.sync
   }
 
+  /// Whether the CPU is between instructions, i.e. the next tick fetches an opcode.
+  #[inline]
+  pub fn instruction_done(&self) -> bool {
+    self.current_instruction.is_none()
+  }
+
+  /// Executes one whole instruction, or the rest of the current one when called mid-instruction.
+  pub fn step(&mut self, memory: &mut dyn Memory) -> RunResult {
+    let start = self.cycle;
+    if self.execution_mode == ExecutionMode::Instruction && self.current_instruction.is_none() {
+      self.execute_instruction(memory);
+      return RunResult {
+        cycles: self.cycle - start,
+        stop_reason: StopReason::InstructionDone,
+      };
+    }
+
+    self.tick(memory);
+    while self.current_instruction.is_some() {
+      self.tick(memory);
+    }
+
+    RunResult {
+      cycles: self.cycle - start,
+      stop_reason: StopReason::InstructionDone,
+    }
+  }
+
+  /// Runs exactly `cycles` cycles, possibly stopping in the middle of an instruction. In
+  /// `ExecutionMode::Instruction` whole instructions are executed while they surely fit in the
+  /// budget, and the remaining cycles are ticked.
+  pub fn run_for_cycles(&mut self, memory: &mut dyn Memory, cycles: usize) -> RunResult {
+    let start = self.cycle;
+    if self.execution_mode == ExecutionMode::Instruction {
+      while self.current_instruction.is_some() && self.cycle - start < cycles {
+        self.tick(memory);
+      }
+      while cycles - (self.cycle - start) >= MAX_INSTRUCTION_CYCLES {
+        self.execute_instruction(memory);
+      }
+    }
+    while self.cycle - start < cycles {
+      self.tick(memory);
+    }
+
+    RunResult {
+      cycles: self.cycle - start,
+      stop_reason: StopReason::CycleBudget,
+    }
+  }
+
+  /// Executes whole instructions until `predicate` holds. It is checked between instructions,
+  /// including before the first one.
+  pub fn run_until<P: FnMut(&CPU) -> bool>(
+    &mut self,
+    memory: &mut dyn Memory,
+    mut predicate: P,
+  ) -> RunResult {
+    let start = self.cycle;
+    if self.current_instruction.is_some() {
+      self.step(memory);
+    }
+    while !predicate(self) {
+      self.step(memory);
+    }
+
+    RunResult {
+      cycles: self.cycle - start,
+      stop_reason: StopReason::Predicate,
+    }
+  }
+
   #[inline]
   fn increment_program_counter(&mut self) {
     self.program_counter = self.program_counter.wrapping_add(1);
@@ -149,7 +364,7 @@ impl CPU {
 
   fn fetch_opcode(&mut self, memory: &dyn Memory) -> (Byte, Word) {
     let addr = self.program_counter;
-    let opcode = memory[addr];
+    let opcode = memory.fetch_opcode(addr);
     self.increment_program_counter();
     self.cycle += 1;
 
@@ -159,7 +374,7 @@ impl CPU {
   fn fetch_address_from(&mut self, addr: Word, memory: &dyn Memory) -> Word {
     let lo = memory[addr];
     self.cycle += 1;
-    let hi = memory[addr + 1];
+    let hi = memory[addr.wrapping_add(1)];
     self.cycle += 1;
 
     Word::from_le_bytes([lo, hi])
@@ -211,41 +426,39 @@ impl CPU {
     STACK_PAGE_HI | (self.stack_pointer as u16)
   }
 
-  fn read_memory(&self, addr_mode: AddressingMode) -> Box<dyn ReadMemoryTasks> {
-    let addressing_tasks: Box<dyn AddressingTasks> = match addr_mode {
-      AddressingMode::ZeroPage => Box::new(ZeroPageAddressingTasks::new()),
-      AddressingMode::ZeroPageX => Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_x()),
-      AddressingMode::ZeroPageY => Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_y()),
-      AddressingMode::Absolute => Box::new(AbsoluteAddressingTasks::new()),
-      AddressingMode::AbsoluteX => Box::new(AbsoluteOffsetAddressingTasks::new(
-        OffsetVariant::X,
-        AccessVariant::Read,
-      )),
-      AddressingMode::AbsoluteY => Box::new(AbsoluteOffsetAddressingTasks::new(
-        OffsetVariant::Y,
-        AccessVariant::Read,
-      )),
+  fn read_memory(&self, addr_mode: AddressingMode) -> AddressingReadMemoryTasks {
+    let addressing_tasks: AddressingModeTasks = match addr_mode {
+      AddressingMode::ZeroPage => ZeroPageAddressingTasks::new().into(),
+      AddressingMode::ZeroPageX => ZeroPageOffsetAddressingTasks::new_offset_by_x().into(),
+      AddressingMode::ZeroPageY => ZeroPageOffsetAddressingTasks::new_offset_by_y().into(),
+      AddressingMode::Absolute => AbsoluteAddressingTasks::new().into(),
+      AddressingMode::AbsoluteX => {
+        AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Read).into()
+      }
+      AddressingMode::AbsoluteY => {
+        AbsoluteOffsetAddressingTasks::new(OffsetVariant::Y, AccessVariant::Read).into()
+      }
       AddressingMode::Indirect => {
         if self.chip_variant == ChipVariant::NMOS {
-          Box::new(IndirectAddressingTasks::new_incorrect_addressing())
+          IndirectAddressingTasks::new_incorrect_addressing().into()
         } else {
-          Box::new(IndirectAddressingTasks::new_fixed_addressing())
+          IndirectAddressingTasks::new_fixed_addressing().into()
         }
       }
-      AddressingMode::IndexIndirectX => Box::new(IndexIndirectXAddressingTasks::new()),
+      AddressingMode::IndexIndirectX => IndexIndirectXAddressingTasks::new().into(),
       AddressingMode::IndirectIndexY => {
-        Box::new(IndirectIndexYAddressingTasks::new(AccessVariant::Read))
+        IndirectIndexYAddressingTasks::new(AccessVariant::Read).into()
       }
-      AddressingMode::Immediate => Box::new(ImmediateReadMemoryTasks::new()),
+      AddressingMode::Immediate => ImmediateReadMemoryTasks::new().into(),
       AddressingMode::Accumulator | AddressingMode::Implicit | AddressingMode::Relative => {
         panic!("addressing tasks not available")
       }
     };
 
     if access_cycle_has_been_done_during_addressing(addr_mode) {
-      Box::new(AddressingReadMemoryTasks::new_with_access_during_addressing(addressing_tasks))
+      AddressingReadMemoryTasks::new_with_access_during_addressing(addressing_tasks)
     } else {
-      Box::new(AddressingReadMemoryTasks::new_with_access_in_separate_cycle(addressing_tasks))
+      AddressingReadMemoryTasks::new_with_access_in_separate_cycle(addressing_tasks)
     }
   }
 
@@ -271,13 +484,53 @@ impl CPU {
   }
 
   fn dummy_fetch(&mut self, memory: &dyn Memory) {
-    let _ = memory[self.program_counter]; // fetch and discard
+    memory.dummy_read(self.program_counter); // fetch and discard
+  }
+
+  // Interrupts are polled between instructions, NMI taking precedence over IRQ.
+  fn take_interrupt(&mut self) -> Option<Interrupt> {
+    if self.nmi_pending {
+      self.nmi_pending = false;
+      Some(Interrupt::Nmi)
+    } else if self.irq && !self.processor_status.get_interrupt_disable_flag() {
+      Some(Interrupt::Irq)
+    } else {
+      None
+    }
+  }
+
+  fn interrupt_vector(&self, interrupt: Interrupt) -> Word {
+    match interrupt {
+      Interrupt::Nmi => self.nmi_vector,
+      Interrupt::Brk | Interrupt::Irq => self.brk_vector,
+    }
+  }
+
+  // The opcode fetched by an interrupt is discarded and BRK is executed in its place.
+  fn schedule_interrupt(
+    &mut self,
+    interrupt: Interrupt,
+    memory: &dyn Memory,
+  ) -> InstructionExecution {
+    let addr = self.program_counter;
+    _ = memory.fetch_opcode(addr);
+    self.cycle += 1;
+    self.addr = Address::new();
+    self.addr.reset_implicit();
+
+    InstructionExecution {
+      addr,
+      tasks: BrkTasks::new(interrupt).into(),
+      opcode: BRK,
+      name: interrupt.name(),
+      starting_cycle: self.cycle,
+    }
   }
 
   fn schedule_instruction(&mut self, memory: &dyn Memory) -> InstructionExecution {
     let (opcode, addr) = self.fetch_opcode(memory);
-    let instruction = INSTRUCTIONS
-      .get(&opcode)
+    let instruction = INSTRUCTIONS[opcode as usize]
+      .as_ref()
       .unwrap_or_else(|| panic!("illegal opcode found: {:#04X}", opcode));
 
     self.addr = Address::new();
@@ -301,12 +554,13 @@ fn access_cycle_has_been_done_during_addressing(addr_mode: AddressingMode) -> bo
     || addr_mode == AddressingMode::Immediate
 }
 
+#[derive(Clone)]
 pub struct InstructionExecution {
   pub addr: Word,
   pub opcode: Byte,
   pub name: &'static str,
   pub starting_cycle: usize,
-  tasks: Box<dyn Tasks>,
+  tasks: InstructionTasks,
 }
 
 #[cfg(test)]
@@ -3,11 +3,21 @@ pub mod address;
 pub mod indirect;
 pub mod zero_page;
 
-use strum::Display;
+use strum::{Display, FromRepr};
 
-use super::{CPU, tasks::Tasks};
+use super::{
+  CPU,
+  save_state::enum_state_field,
+  tasks::{Tasks, read_memory::ImmediateReadMemoryTasks, tasks_enum},
+};
+use absolute::{AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks};
+use indirect::{
+  IndexIndirectXAddressingTasks, IndirectAddressingTasks, IndirectIndexYAddressingTasks,
+};
+use zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks};
 
-#[derive(Debug, Copy, Clone, PartialEq, Display)]
+#[derive(Debug, Copy, Clone, PartialEq, Display, FromRepr)]
+#[repr(u8)]
 pub enum AddressingMode {
   Absolute,
   AbsoluteX,
@@ -24,6 +34,9 @@ pub enum AddressingMode {
   ZeroPageY,
 }
 
+enum_state_field!(AddressingMode);
+
+#[derive(Clone)]
 pub enum OffsetVariant {
   X,
   Y,
@@ -32,3 +45,32 @@ pub enum OffsetVariant {
 pub trait AddressingTasks: Tasks {
   fn fetch_during_addressing(&self) -> bool;
 }
+
+tasks_enum! {
+  #[derive(Clone)]
+  pub enum AddressingModeTasks {
+    ZeroPage(ZeroPageAddressingTasks),
+    ZeroPageOffset(ZeroPageOffsetAddressingTasks),
+    Absolute(AbsoluteAddressingTasks),
+    AbsoluteOffset(AbsoluteOffsetAddressingTasks),
+    Indirect(IndirectAddressingTasks),
+    IndexIndirectX(IndexIndirectXAddressingTasks),
+    IndirectIndexY(IndirectIndexYAddressingTasks),
+    Immediate(ImmediateReadMemoryTasks),
+  }
+}
+
+impl AddressingTasks for AddressingModeTasks {
+  fn fetch_during_addressing(&self) -> bool {
+    match self {
+      AddressingModeTasks::ZeroPage(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::ZeroPageOffset(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::Absolute(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::AbsoluteOffset(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::Indirect(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::IndexIndirectX(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::IndirectIndexY(tasks) => tasks.fetch_during_addressing(),
+      AddressingModeTasks::Immediate(tasks) => tasks.fetch_during_addressing(),
+    }
+  }
+}
@@ -1,12 +1,19 @@
+use strum::FromRepr;
+
 use crate::{
   consts::Word,
-  cpu::{addressing::AddressingTasks, tasks::Tasks},
+  cpu::{
+    addressing::AddressingTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+    tasks::Tasks,
+  },
   memory::Memory,
 };
 
 use super::OffsetVariant;
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum AbsoluteOffsetStep {
   MemoryAccessLo,
   MemoryAccessHiOffsetLo,
@@ -15,13 +22,16 @@ enum AbsoluteOffsetStep {
   Done,
 }
 
-#[derive(Eq, PartialEq)]
+enum_state_field!(AbsoluteOffsetStep);
+
+#[derive(Clone, Eq, PartialEq)]
 pub enum AccessVariant {
   Read,
   Modify,
   Write,
 }
 
+#[derive(Clone)]
 pub struct AbsoluteOffsetAddressingTasks {
   step: AbsoluteOffsetStep,
   pub offset_variant: OffsetVariant,
@@ -98,7 +108,7 @@ impl Tasks for AbsoluteOffsetAddressingTasks {
           .expect("unexpected lack of indirect address in FixHi step")
           .to_le_bytes();
         let tgt_addr = Word::from_le_bytes([lo, hi]);
-        _ = memory[tgt_addr]; // dummy read
+        memory.dummy_read(tgt_addr); // dummy read
 
         if self.access_variant == AccessVariant::Read && !self.carry {
           cpu.addr.done = true;
@@ -124,7 +134,7 @@ impl Tasks for AbsoluteOffsetAddressingTasks {
           .addr
           .value()
           .expect("unexpected lack of indirect address in Refetch step");
-        _ = memory[tgt_addr]; // dummy read
+        memory.dummy_read(tgt_addr); // dummy read
 
         cpu.addr.done = true;
         self.step = AbsoluteOffsetStep::Done;
@@ -135,15 +145,30 @@ impl Tasks for AbsoluteOffsetAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+    state.write(&self.carry);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    self.carry = state.read()?;
+    Ok(())
+  }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum AbsoluteStep {
   MemoryLo,
   MemoryHi,
   Done,
 }
 
+enum_state_field!(AbsoluteStep);
+
+#[derive(Clone)]
 pub struct AbsoluteAddressingTasks {
   step: AbsoluteStep,
 }
@@ -192,6 +217,15 @@ impl Tasks for AbsoluteAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
 #[cfg(test)]
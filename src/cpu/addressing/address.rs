@@ -1,6 +1,9 @@
 use crate::{
   consts::{Byte, Word},
-  cpu::addressing::AddressingMode,
+  cpu::{
+    addressing::AddressingMode,
+    save_state::{SaveStateError, StateField, StateReader, StateWriter},
+  },
 };
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -11,6 +14,24 @@ pub struct Address {
   pub done: bool,
 }
 
+impl StateField for Address {
+  fn write(&self, state: &mut StateWriter) {
+    state.write(&self.indirect);
+    state.write(&self.val);
+    state.write(&self.mode);
+    state.write(&self.done);
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    Ok(Address {
+      indirect: state.read()?,
+      val: state.read()?,
+      mode: state.read()?,
+      done: state.read()?,
+    })
+  }
+}
+
 impl Address {
   pub fn new() -> Self {
     Address {
@@ -1,13 +1,17 @@
+use strum::FromRepr;
+
 use crate::{
   consts::{Byte, Word},
   cpu::{
     addressing::{AddressingMode, AddressingTasks, absolute::AccessVariant},
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
     tasks::Tasks,
   },
   memory::Memory,
 };
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum IndirectIndexYStep {
   PointerAddrFetch,
   IndirectAccessLo,
@@ -17,6 +21,9 @@ enum IndirectIndexYStep {
   Done,
 }
 
+enum_state_field!(IndirectIndexYStep);
+
+#[derive(Clone)]
 pub struct IndirectIndexYAddressingTasks {
   step: IndirectIndexYStep,
   carry: bool,
@@ -92,7 +99,7 @@ impl Tasks for IndirectIndexYAddressingTasks {
           .addr
           .value()
           .expect("unexpected lack of address in OffsetLo step");
-        _ = memory[tgt_addr]; // dummy fetch;
+        memory.dummy_read(tgt_addr); // dummy fetch;
 
         if !self.carry {
           cpu.addr.done = true;
@@ -120,7 +127,7 @@ impl Tasks for IndirectIndexYAddressingTasks {
           .addr
           .value()
           .expect("unexpected lack of address in OffsetHi step");
-        _ = memory[tgt_addr]; // dummy refetch;
+        memory.dummy_read(tgt_addr); // dummy refetch;
 
         cpu.addr.done = true;
         self.step = IndirectIndexYStep::Done;
@@ -131,9 +138,21 @@ impl Tasks for IndirectIndexYAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+    state.write(&self.carry);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    self.carry = state.read()?;
+    Ok(())
+  }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum IndexIndirectXStep {
   IndirectAccess,
   SumWithX,
@@ -142,6 +161,9 @@ enum IndexIndirectXStep {
   Done,
 }
 
+enum_state_field!(IndexIndirectXStep);
+
+#[derive(Clone)]
 pub struct IndexIndirectXAddressingTasks {
   step: IndexIndirectXStep,
   tgt_addr_lo: u8,
@@ -183,7 +205,7 @@ impl Tasks for IndexIndirectXAddressingTasks {
           .addr
           .indirect()
           .expect("unexpected lack of indirect address in SumWithX step");
-        _ = memory[addr_output]; // dummy read
+        memory.dummy_read(addr_output); // dummy read
         self.tgt_addr_lo = addr_output.to_le_bytes()[0].wrapping_add(cpu.index_register_x);
         self.step = IndexIndirectXStep::MemoryAccessLo;
 
@@ -211,9 +233,21 @@ impl Tasks for IndexIndirectXAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+    state.write(&self.tgt_addr_lo);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    self.tgt_addr_lo = state.read()?;
+    Ok(())
+  }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum IndirectStep {
   IndirectFetchLo,
   IndirectFetchHi,
@@ -224,6 +258,9 @@ enum IndirectStep {
   Done,
 }
 
+enum_state_field!(IndirectStep);
+
+#[derive(Clone)]
 pub struct IndirectAddressingTasks {
   fixed_addressing: bool,
   step: IndirectStep,
@@ -303,7 +340,7 @@ impl Tasks for IndirectAddressingTasks {
           .addr
           .indirect()
           .expect("indirect address is unexpectedly empty");
-        let addr_hi = memory[addr + 1];
+        let addr_hi = memory[addr.wrapping_add(1)];
         cpu.addr.set_hi(addr_hi);
         cpu.addr.done = true;
         self.step = IndirectStep::Done;
@@ -332,6 +369,15 @@ impl Tasks for IndirectAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -1,11 +1,18 @@
+use strum::FromRepr;
+
 use crate::{
   consts::Byte,
-  cpu::{addressing::AddressingTasks, tasks::Tasks},
+  cpu::{
+    addressing::AddressingTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+    tasks::Tasks,
+  },
   memory::Memory,
 };
 
 use super::OffsetVariant;
 
+#[derive(Clone)]
 pub struct ZeroPageAddressingTasks {
   done: bool,
 }
@@ -41,15 +48,28 @@ impl Tasks for ZeroPageAddressingTasks {
     self.done = true;
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    Ok(())
+  }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, FromRepr)]
+#[repr(u8)]
 enum ZeroPageOffsetStep {
   ZeroPageAccess,
   Offset,
   Done,
 }
 
+enum_state_field!(ZeroPageOffsetStep);
+
+#[derive(Clone)]
 pub struct ZeroPageOffsetAddressingTasks {
   step: ZeroPageOffsetStep,
   variant: OffsetVariant,
@@ -107,7 +127,7 @@ impl Tasks for ZeroPageOffsetAddressingTasks {
           .value()
           .expect("unexpected lack of address at Offset step") as Byte;
 
-        _ = memory[addr_output.into()]; // dummy fetch from address
+        memory.dummy_read(addr_output.into()); // dummy fetch from address
         let final_address = addr_output.wrapping_add(offset);
         cpu.addr.set(final_address);
 
@@ -121,6 +141,15 @@ impl Tasks for ZeroPageOffsetAddressingTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,298 @@
+use std::{
+  any::Any,
+  panic::{self, AssertUnwindSafe},
+  sync::Mutex,
+};
+
+use crate::{
+  cpu::{CPU, CpuState, ExecutionMode, StopReason},
+  memory::Generic64kMem,
+};
+
+type StopCondition = Box<dyn Fn(&CPU) -> bool + Send>;
+
+/// Program run by `run_batch`, starting from `state` with `memory`.
+pub struct BatchJob {
+  memory: Generic64kMem,
+  state: CpuState,
+  cycle_limit: usize,
+  execution_mode: ExecutionMode,
+  stop_condition: Option<StopCondition>,
+  trace: bool,
+}
+
+impl BatchJob {
+  pub fn new(memory: Generic64kMem, state: CpuState, cycle_limit: usize) -> Self {
+    BatchJob {
+      memory,
+      state,
+      cycle_limit,
+      execution_mode: ExecutionMode::default(),
+      stop_condition: None,
+      trace: false,
+    }
+  }
+
+  pub fn execution_mode(mut self, mode: ExecutionMode) -> Self {
+    self.execution_mode = mode;
+    self
+  }
+
+  /// Stops the job once `condition` holds. It is checked between instructions, including before
+  /// the first one.
+  pub fn stop_when<C: Fn(&CPU) -> bool + Send + 'static>(mut self, condition: C) -> Self {
+    self.stop_condition = Some(Box::new(condition));
+    self
+  }
+
+  /// Records the state before every executed instruction.
+  pub fn with_trace(mut self) -> Self {
+    self.trace = true;
+    self
+  }
+
+  fn run(self) -> BatchResult {
+    let mut memory = self.memory;
+    let mut cpu = CPU::new(self.state.variant);
+    cpu.set_state(self.state);
+    cpu.set_execution_mode(self.execution_mode);
+    let mut trace = self.trace.then(Vec::new);
+
+    let start = cpu.cycle;
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+      loop {
+        if let Some(condition) = &self.stop_condition
+          && condition(&cpu)
+        {
+          break StopReason::Predicate;
+        }
+        if cpu.cycle - start >= self.cycle_limit {
+          break StopReason::CycleBudget;
+        }
+
+        if let Some(trace) = &mut trace {
+          trace.push(cpu.state());
+        }
+        cpu.step(&mut memory);
+      }
+    }));
+    let outcome = match outcome {
+      Ok(stop_reason) => BatchOutcome::Stopped(stop_reason),
+      Err(payload) => BatchOutcome::Panicked(panic_message(payload)),
+    };
+
+    BatchResult {
+      state: cpu.state(),
+      cycles: cpu.cycle - start,
+      outcome,
+      trace,
+      memory,
+    }
+  }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+  match payload.downcast::<String>() {
+    Ok(message) => *message,
+    Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+      || String::from("unknown panic"),
+      |message| message.to_string(),
+    ),
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchOutcome {
+  Stopped(StopReason),
+  /// The job panicked, e.g. on an illegal opcode, with the panic message.
+  Panicked(String),
+}
+
+pub struct BatchResult {
+  /// State when the job stopped. After a panic it's the state the CPU was left in, possibly
+  /// in the middle of an instruction.
+  pub state: CpuState,
+  pub cycles: usize,
+  pub outcome: BatchOutcome,
+  pub trace: Option<Vec<CpuState>>,
+  pub memory: Generic64kMem,
+}
+
+/// Runs `jobs` on up to `threads` threads and returns their results in the order of `jobs`.
+/// Jobs execute whole instructions, so the one crossing the cycle limit is completed. A job
+/// panicking, e.g. on an illegal opcode, ends with `BatchOutcome::Panicked` without affecting
+/// the others. Results don't depend on the number of threads.
+///
+/// Panics are still reported by the panic hook, which prints them to stderr by default. To keep
+/// the output quiet when many jobs are expected to fail, install a silent hook around the batch
+/// with `std::panic::set_hook(Box::new(|_| {}))` and restore it with `std::panic::take_hook`.
+pub fn run_batch(jobs: Vec<BatchJob>, threads: usize) -> Vec<BatchResult> {
+  let job_count = jobs.len();
+  let queue = Mutex::new(jobs.into_iter().enumerate());
+  let next_job = || queue.lock().unwrap().next();
+
+  let mut finished: Vec<(usize, BatchResult)> = std::thread::scope(|scope| {
+    let workers: Vec<_> = (0..threads.clamp(1, job_count.max(1)))
+      .map(|_| {
+        scope.spawn(|| {
+          let mut finished = Vec::new();
+          while let Some((idx, job)) = next_job() {
+            finished.push((idx, job.run()));
+          }
+          finished
+        })
+      })
+      .collect();
+
+    workers
+      .into_iter()
+      .flat_map(|worker| worker.join().unwrap())
+      .collect()
+  });
+
+  finished.sort_unstable_by_key(|(idx, _)| *idx);
+  finished.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod run_batch {
+    use crate::{
+      consts::Word,
+      cpu::{
+        CPU, ChipVariant, CpuState, ExecutionMode, StopReason,
+        batch::{BatchJob, BatchOutcome, run_batch},
+      },
+      memory::Generic64kMem,
+    };
+
+    fn job(counter: u8) -> BatchJob {
+      let mut memory = Generic64kMem::new();
+      // loop: LDX #counter, DEX, BNE -3, BRK
+      memory.insert(0x0200, &[0xA2, counter, 0xCA, 0xD0, 0xFD, 0x00]);
+      let state = CpuState {
+        pc: 0x0200,
+        sp: 0xFF,
+        a: 0,
+        x: 0,
+        y: 0,
+        p: 0x20,
+        cycle: 0,
+        variant: ChipVariant::NMOS,
+      };
+
+      BatchJob::new(memory, state, 10_000).stop_when(|cpu: &CPU| cpu.state().pc == 0x0205)
+    }
+
+    #[test]
+    fn should_return_results_in_order_of_jobs() {
+      let results = run_batch((1..=20).map(job).collect(), 4);
+
+      assert_eq!(results.len(), 20);
+      for (counter, result) in (1..=20).zip(results) {
+        assert_eq!(result.outcome, BatchOutcome::Stopped(StopReason::Predicate));
+        assert_eq!(result.state.pc, 0x0205);
+        // LDX, then DEX and BNE for every iteration with the last branch not taken
+        assert_eq!(result.cycles, 2 + counter * 5 - 1);
+      }
+    }
+
+    #[test]
+    fn should_report_panicking_job_and_finish_others() {
+      let mut memory = Generic64kMem::new();
+      // NOP, illegal opcode
+      memory.insert(0x0200, &[0xEA, 0x02]);
+      let state = CpuState {
+        pc: 0x0200,
+        ..CPU::new_nmos().state()
+      };
+      let jobs = vec![job(3), BatchJob::new(memory, state, 100), job(5)];
+
+      let results = run_batch(jobs, 2);
+
+      assert_eq!(
+        results[1].outcome,
+        BatchOutcome::Panicked(String::from("illegal opcode found: 0x02"))
+      );
+      assert_eq!(results[1].state.pc, 0x0202);
+      for idx in [0, 2] {
+        assert_eq!(
+          results[idx].outcome,
+          BatchOutcome::Stopped(StopReason::Predicate)
+        );
+      }
+    }
+
+    #[test]
+    fn should_not_depend_on_number_of_threads() {
+      let summary = |threads| {
+        run_batch((0..=40).map(|counter| job(counter * 6)).collect(), threads)
+          .into_iter()
+          .map(|result| (result.state, result.cycles, result.outcome))
+          .collect::<Vec<_>>()
+      };
+
+      let single = summary(1);
+      assert_eq!(summary(3), single);
+      assert_eq!(summary(16), single);
+    }
+
+    #[test]
+    fn should_stop_when_cycle_limit_is_reached() {
+      let mut memory = Generic64kMem::new();
+      // JMP $0200
+      memory.insert(0x0200, &[0x4C, 0x00, 0x02]);
+      let state = CPU::new_nmos().state();
+      let jobs = vec![
+        BatchJob::new(
+          memory,
+          CpuState {
+            pc: 0x0200,
+            ..state
+          },
+          10,
+        )
+        .execution_mode(ExecutionMode::Instruction),
+      ];
+
+      let results = run_batch(jobs, 2);
+
+      assert_eq!(
+        results[0].outcome,
+        BatchOutcome::Stopped(StopReason::CycleBudget)
+      );
+      assert_eq!(results[0].cycles, 12);
+    }
+
+    #[test]
+    fn should_record_state_before_each_instruction() {
+      let results = run_batch(vec![job(2).with_trace()], 1);
+
+      let trace: Vec<Word> = results[0]
+        .trace
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|state| state.pc)
+        .collect();
+      assert_eq!(trace, [0x0200, 0x0202, 0x0203, 0x0202, 0x0203]);
+      assert!(run_batch(vec![job(2)], 1)[0].trace.is_none());
+    }
+
+    #[test]
+    fn should_keep_memory_of_each_job() {
+      let mut memory = Generic64kMem::new();
+      // LDA #$42, STA $10
+      memory.insert(0x0200, &[0xA9, 0x42, 0x85, 0x10]);
+      let state = CpuState {
+        pc: 0x0200,
+        ..CPU::new_nmos().state()
+      };
+
+      let results = run_batch(vec![BatchJob::new(memory, state, 5)], 1);
+
+      assert_eq!(results[0].memory.data[0x10], 0x42);
+    }
+  }
+}
@@ -0,0 +1,255 @@
+use crate::{
+  consts::{
+    BRK_INTERRUPT_VECTOR, Byte, DEFAULT_INSTRUCTION_HISTORY_CAPACITY, NMI_INTERRUPT_VECTOR,
+    RESET_VECTOR, Word,
+  },
+  cpu::{CPU, ChipVariant, ExecutionMode, debugger::Debugger},
+  memory::Memory,
+};
+
+/// Configures a `CPU` and its `Debugger`. Unless disabled, the reset sequence runs first and
+/// registers set explicitly are applied on top of it.
+#[derive(Debug, Clone)]
+pub struct CpuBuilder {
+  variant: ChipVariant,
+  execution_mode: ExecutionMode,
+  reset: bool,
+  reset_vector: Word,
+  brk_vector: Word,
+  nmi_vector: Word,
+  history_capacity: usize,
+  pc: Option<Word>,
+  sp: Option<Byte>,
+  a: Option<Byte>,
+  x: Option<Byte>,
+  y: Option<Byte>,
+  p: Option<Byte>,
+}
+
+impl Default for CpuBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CpuBuilder {
+  pub fn new() -> Self {
+    CpuBuilder {
+      variant: ChipVariant::NMOS,
+      execution_mode: ExecutionMode::default(),
+      reset: true,
+      reset_vector: RESET_VECTOR,
+      brk_vector: BRK_INTERRUPT_VECTOR,
+      nmi_vector: NMI_INTERRUPT_VECTOR,
+      history_capacity: DEFAULT_INSTRUCTION_HISTORY_CAPACITY,
+      pc: None,
+      sp: None,
+      a: None,
+      x: None,
+      y: None,
+      p: None,
+    }
+  }
+
+  pub fn variant(mut self, variant: ChipVariant) -> Self {
+    self.variant = variant;
+    self
+  }
+
+  pub fn execution_mode(mut self, mode: ExecutionMode) -> Self {
+    self.execution_mode = mode;
+    self
+  }
+
+  pub fn reset(mut self, reset: bool) -> Self {
+    self.reset = reset;
+    self
+  }
+
+  pub fn reset_vector(mut self, addr: Word) -> Self {
+    self.reset_vector = addr;
+    self
+  }
+
+  pub fn brk_vector(mut self, addr: Word) -> Self {
+    self.brk_vector = addr;
+    self
+  }
+
+  pub fn nmi_vector(mut self, addr: Word) -> Self {
+    self.nmi_vector = addr;
+    self
+  }
+
+  pub fn history_capacity(mut self, capacity: usize) -> Self {
+    self.history_capacity = capacity;
+    self
+  }
+
+  pub fn pc(mut self, pc: Word) -> Self {
+    self.pc = Some(pc);
+    self
+  }
+
+  pub fn sp(mut self, sp: Byte) -> Self {
+    self.sp = Some(sp);
+    self
+  }
+
+  pub fn a(mut self, a: Byte) -> Self {
+    self.a = Some(a);
+    self
+  }
+
+  pub fn x(mut self, x: Byte) -> Self {
+    self.x = Some(x);
+    self
+  }
+
+  pub fn y(mut self, y: Byte) -> Self {
+    self.y = Some(y);
+    self
+  }
+
+  pub fn p(mut self, p: Byte) -> Self {
+    self.p = Some(p);
+    self
+  }
+
+  pub fn build(&self, memory: &dyn Memory) -> CPU {
+    let mut cpu = CPU::new(self.variant);
+    cpu.reset_vector = self.reset_vector;
+    cpu.brk_vector = self.brk_vector;
+    cpu.nmi_vector = self.nmi_vector;
+    cpu.execution_mode = self.execution_mode;
+    if self.reset {
+      cpu.reset(memory);
+    }
+
+    let mut state = cpu.state();
+    state.pc = self.pc.unwrap_or(state.pc);
+    state.sp = self.sp.unwrap_or(state.sp);
+    state.a = self.a.unwrap_or(state.a);
+    state.x = self.x.unwrap_or(state.x);
+    state.y = self.y.unwrap_or(state.y);
+    state.p = self.p.unwrap_or(state.p);
+    cpu.set_state(state);
+
+    cpu
+  }
+
+  pub fn debugger(&self) -> Debugger {
+    Debugger::with_capacity(self.history_capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod build {
+    use crate::{
+      cpu::{ChipVariant, CpuState, builder::CpuBuilder},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_run_reset_sequence_from_configured_vector() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0xFFF0, &[0x00, 0x06]);
+
+      let uut = CpuBuilder::new()
+        .variant(ChipVariant::RockwellCMOS)
+        .reset_vector(0xFFF0)
+        .build(&memory);
+
+      assert_eq!(uut.state().pc, 0x0600);
+      assert_eq!(uut.state().p, 0b00100100);
+      assert_eq!(uut.state().variant, ChipVariant::RockwellCMOS);
+    }
+
+    #[test]
+    fn should_wrap_around_when_reading_vector_at_end_of_memory() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0xFFFF, &[0x00]);
+      memory.insert(0x0000, &[0x06]);
+
+      let uut = CpuBuilder::new().reset_vector(0xFFFF).build(&memory);
+
+      assert_eq!(uut.state().pc, 0x0600);
+    }
+
+    #[test]
+    fn should_apply_registers_without_reset() {
+      let memory = Generic64kMem::new();
+
+      let uut = CpuBuilder::new()
+        .reset(false)
+        .pc(0x0200)
+        .sp(0xFF)
+        .a(0x01)
+        .x(0x02)
+        .y(0x03)
+        .p(0b00000001)
+        .build(&memory);
+
+      assert_eq!(
+        uut.state(),
+        CpuState {
+          pc: 0x0200,
+          sp: 0xFF,
+          a: 0x01,
+          x: 0x02,
+          y: 0x03,
+          p: 0b00100001,
+          cycle: 0,
+          variant: ChipVariant::NMOS
+        }
+      );
+    }
+
+    #[test]
+    fn should_jump_through_configured_brk_vector() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0xFFF8, &[0x00, 0x90]);
+      let mut uut = CpuBuilder::new()
+        .reset(false)
+        .pc(0x0600)
+        .sp(0xFF)
+        .brk_vector(0xFFF8)
+        .build(&memory);
+
+      uut.step(&mut memory);
+
+      assert_eq!(uut.state().pc, 0x9000);
+    }
+  }
+
+  #[cfg(test)]
+  mod debugger {
+    use crate::{
+      cpu::{builder::CpuBuilder, utils::execute_next_instruction},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_keep_configured_number_of_instructions() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0600, &[0xEA, 0xE8, 0xC8]);
+      let builder = CpuBuilder::new()
+        .reset(false)
+        .pc(0x0600)
+        .history_capacity(2);
+      let mut cpu = builder.build(&memory);
+      let mut uut = builder.debugger();
+
+      for _ in 0..3 {
+        execute_next_instruction(&mut cpu, &mut memory, Some(&mut uut));
+      }
+
+      assert_eq!(
+        uut.history().map(|info| info.name).collect::<Vec<_>>(),
+        vec!["INX", "INY"]
+      );
+    }
+  }
+}
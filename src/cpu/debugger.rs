@@ -6,14 +6,22 @@ use crate::{
   consts::{Byte, DEFAULT_INSTRUCTION_HISTORY_CAPACITY, Word},
   cpu::{
     CPU,
-    debugger::{debug_instruction_info::DebugInstructionInfo, registers::Registers},
+    debugger::{
+      breakpoint::{Breakpoint, BreakpointId},
+      debug_instruction_info::DebugInstructionInfo,
+      registers::Registers,
+      watchpoint::{WatchKind, Watchpoint, WatchpointId},
+    },
     processor_status::ProcessorStatus,
   },
-  memory::Memory,
+  memory::{BusAccessKind, Memory, MemoryFault},
 };
 
+pub mod breakpoint;
 pub mod debug_instruction_info;
 pub mod registers;
+pub mod symbol_table;
+pub mod watchpoint;
 
 #[derive(Debug, PartialEq)]
 pub enum Traps {
@@ -38,7 +46,34 @@ pub enum ProbeEvent {
   InstructionDone,
   AddressingDone,
   TrapHit(Traps),
-  MemoryModification((Byte, Byte)),
+  /// Breakpoint fired before the instruction at `pc` executes.
+  BreakpointHit {
+    id: BreakpointId,
+    pc: Word,
+  },
+  WatchpointHit(WatchpointHit),
+  /// Fault reported by the memory, with the instruction that caused it.
+  MemoryFault {
+    pc: Word,
+    instruction: Option<&'static str>,
+    fault: MemoryFault,
+  },
+}
+
+/// Access that fired a watchpoint, with the instruction that made it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchpointHit {
+  pub id: WatchpointId,
+  pub kind: BusAccessKind,
+  pub pc: Word,
+  pub instruction: Option<&'static str>,
+  /// Cycle count of the CPU when the access was probed.
+  pub cycle: usize,
+  pub addr: Word,
+  pub old: Byte,
+  pub new: Byte,
+  /// Only resolved by `probe_with_symbols`.
+  pub symbol: Option<String>,
 }
 
 pub trait Symbols {
@@ -48,13 +83,29 @@ pub trait Symbols {
 pub struct Debugger {
   instructions: AllocRingBuffer<DebugInstructionInfo>,
   traps: Vec<TrapConditions>,
+  breakpoints: Vec<(BreakpointId, Breakpoint)>,
+  next_breakpoint_id: BreakpointId,
+  // cycle at which breakpoints were last checked, so probing twice doesn't count hits twice
+  breakpoints_checked_at: Option<usize>,
+  watchpoints: Vec<(WatchpointId, Watchpoint)>,
+  next_watchpoint_id: WatchpointId,
 }
 
 impl Debugger {
   pub fn new() -> Self {
+    Self::with_capacity(DEFAULT_INSTRUCTION_HISTORY_CAPACITY)
+  }
+
+  /// Creates a debugger keeping up to `capacity` last instructions.
+  pub fn with_capacity(capacity: usize) -> Self {
     Debugger {
-      instructions: AllocRingBuffer::new(DEFAULT_INSTRUCTION_HISTORY_CAPACITY),
+      instructions: AllocRingBuffer::new(capacity.max(1)),
       traps: Vec::new(),
+      breakpoints: Vec::new(),
+      next_breakpoint_id: 0,
+      breakpoints_checked_at: None,
+      watchpoints: Vec::new(),
+      next_watchpoint_id: 0,
     }
   }
 
@@ -69,6 +120,8 @@ impl Debugger {
       processor_status: cpu.processor_status,
     };
 
+    result.events.extend(self.check_breakpoints(cpu));
+
     if cpu.current_instruction.is_none() && cpu.cycle > 0 {
       result.events.push(ProbeEvent::InstructionDone);
     } else if cpu.sync()
@@ -87,6 +140,38 @@ impl Debugger {
       result.events.push(ProbeEvent::NextInstruction);
     }
 
+    let (pc, instruction) = self
+      .instructions
+      .back()
+      .map_or((cpu.program_counter, None), |instruction| {
+        (instruction.addr, Some(instruction.name))
+      });
+    for fault in memory.take_faults() {
+      result.events.push(ProbeEvent::MemoryFault {
+        pc,
+        instruction,
+        fault,
+      });
+    }
+
+    for access in memory.take_accesses() {
+      for (id, watchpoint) in self.watchpoints.iter_mut() {
+        if watchpoint.hit(&access) {
+          result.events.push(ProbeEvent::WatchpointHit(WatchpointHit {
+            id: *id,
+            kind: access.kind,
+            pc,
+            instruction,
+            cycle: cpu.cycle,
+            addr: access.addr,
+            old: access.old,
+            new: access.new,
+            symbol: None,
+          }));
+        }
+      }
+    }
+
     let Some(last_instruction) = &mut self.instructions.back_mut() else {
       return result;
     };
@@ -95,7 +180,7 @@ impl Debugger {
     let addressing_done = last_instruction.target_addr.is_none() && cpu.addr.done;
     if addressing_done {
       if let Some(addr) = target_addr.value() {
-        last_instruction.target_val = Some(memory[addr])
+        last_instruction.target_val = Some(memory.peek(addr))
       }
 
       last_instruction.target_addr = Some(target_addr);
@@ -127,7 +212,13 @@ impl Debugger {
     memory: &dyn Memory,
     symbols: &S,
   ) -> ProbeResult {
-    let result = self.probe(cpu, memory);
+    let mut result = self.probe(cpu, memory);
+    for event in result.events.iter_mut() {
+      if let ProbeEvent::WatchpointHit(hit) = event {
+        hit.symbol = symbols.get(&hit.addr);
+      }
+    }
+
     let Some(last_instruction) = &mut self.instructions.back_mut() else {
       return result;
     };
@@ -150,9 +241,118 @@ impl Debugger {
     self.instructions.back()
   }
 
+  /// Instructions kept in the history, oldest first.
+  pub fn history(&self) -> impl Iterator<Item = &DebugInstructionInfo> {
+    self.instructions.iter()
+  }
+
   pub fn trap_between_addresses(&mut self, addrs: RangeInclusive<Word>) {
     self.traps.push(TrapConditions::AddressRange(addrs))
   }
+
+  /// Breaks before an instruction within `addrs` executes.
+  pub fn add_breakpoint(&mut self, addrs: RangeInclusive<Word>) -> BreakpointId {
+    self.insert_breakpoint(Breakpoint::new(addrs))
+  }
+
+  /// Breaks once before an instruction within `addrs` executes.
+  pub fn add_temporary_breakpoint(&mut self, addrs: RangeInclusive<Word>) -> BreakpointId {
+    self.insert_breakpoint(Breakpoint {
+      temporary: true,
+      ..Breakpoint::new(addrs)
+    })
+  }
+
+  pub fn remove_breakpoint(&mut self, id: BreakpointId) -> Option<Breakpoint> {
+    let idx = self
+      .breakpoints
+      .iter()
+      .position(|(bp_id, _)| *bp_id == id)?;
+    Some(self.breakpoints.remove(idx).1)
+  }
+
+  pub fn breakpoint(&self, id: BreakpointId) -> Option<&Breakpoint> {
+    self
+      .breakpoints
+      .iter()
+      .find_map(|(bp_id, bp)| (*bp_id == id).then_some(bp))
+  }
+
+  pub fn breakpoint_mut(&mut self, id: BreakpointId) -> Option<&mut Breakpoint> {
+    self
+      .breakpoints
+      .iter_mut()
+      .find_map(|(bp_id, bp)| (*bp_id == id).then_some(bp))
+  }
+
+  pub fn breakpoints(&self) -> impl Iterator<Item = (BreakpointId, &Breakpoint)> {
+    self.breakpoints.iter().map(|(id, bp)| (*id, bp))
+  }
+
+  /// Reports accesses of `kind` to addresses within `addrs`. Accesses are only seen through
+  /// memories monitoring the bus, like `BusMonitor`; on any other memory, e.g. a plain
+  /// `Generic64kMem` or `MemoryMap`, the watchpoint never fires.
+  pub fn add_watchpoint(&mut self, addrs: RangeInclusive<Word>, kind: WatchKind) -> WatchpointId {
+    let id = self.next_watchpoint_id;
+    self.next_watchpoint_id += 1;
+    self.watchpoints.push((id, Watchpoint::new(addrs, kind)));
+    id
+  }
+
+  pub fn remove_watchpoint(&mut self, id: WatchpointId) -> Option<Watchpoint> {
+    let idx = self
+      .watchpoints
+      .iter()
+      .position(|(wp_id, _)| *wp_id == id)?;
+    Some(self.watchpoints.remove(idx).1)
+  }
+
+  pub fn watchpoint(&self, id: WatchpointId) -> Option<&Watchpoint> {
+    self
+      .watchpoints
+      .iter()
+      .find_map(|(wp_id, wp)| (*wp_id == id).then_some(wp))
+  }
+
+  pub fn watchpoint_mut(&mut self, id: WatchpointId) -> Option<&mut Watchpoint> {
+    self
+      .watchpoints
+      .iter_mut()
+      .find_map(|(wp_id, wp)| (*wp_id == id).then_some(wp))
+  }
+
+  pub fn watchpoints(&self) -> impl Iterator<Item = (WatchpointId, &Watchpoint)> {
+    self.watchpoints.iter().map(|(id, wp)| (*id, wp))
+  }
+
+  /// Returns the breakpoints firing at the program counter. `probe` checks them after every
+  /// instruction, this is for checking before the first one, e.g. at the reset target. A cycle
+  /// is only checked once, so resuming from a breakpoint doesn't fire it again.
+  pub fn check_breakpoints(&mut self, cpu: &CPU) -> Vec<ProbeEvent> {
+    let mut events = Vec::new();
+    if cpu.current_instruction.is_some() || self.breakpoints_checked_at == Some(cpu.cycle) {
+      return events;
+    }
+    self.breakpoints_checked_at = Some(cpu.cycle);
+
+    let pc = cpu.program_counter;
+    self.breakpoints.retain_mut(|(id, breakpoint)| {
+      if !breakpoint.hit(pc) {
+        return true;
+      }
+
+      events.push(ProbeEvent::BreakpointHit { id: *id, pc });
+      !breakpoint.temporary
+    });
+    events
+  }
+
+  fn insert_breakpoint(&mut self, breakpoint: Breakpoint) -> BreakpointId {
+    let id = self.next_breakpoint_id;
+    self.next_breakpoint_id += 1;
+    self.breakpoints.push((id, breakpoint));
+    id
+  }
 }
 
 impl Default for Debugger {
@@ -247,9 +447,10 @@ mod tests {
       CPU,
       addressing::address::Address,
       debugger::{Debugger, ProbeEvent, Registers},
-      instructions::{LDA_A, LDA_IM, LDX_A, LDX_IM, LDY_A, LDY_IM, NOP},
+      instructions::{LDA_A, LDA_IM, LDX_A, LDX_IM, LDY_A, LDY_IM, NOP, PHA, PLA, STA_A},
       tests::MemoryMock,
     };
+    use crate::memory::{Generic64kMem, MemoryFault, WriteProtection};
 
     #[test]
     fn should_return_instruction_done_on_last_cycle_of_instruction() {
@@ -412,6 +613,64 @@ mod tests {
       assert!(result.events.iter().all(none_of_traps_hit_check));
     }
 
+    #[test]
+    fn should_return_memory_fault_with_pc_of_instruction_writing_protected_memory() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0600, &[NOP, STA_A, 0x00, 0xE0]);
+      memory.protect(0xE000..=0xFFFF, WriteProtection::Fault);
+      let mut cpu = CPU::new_nmos();
+      cpu.program_counter = 0x0600;
+      cpu.addr = Address::new();
+
+      let mut uut = Debugger::new();
+
+      let mut events = Vec::new();
+      for _ in 0..6 {
+        cpu.tick(&mut memory);
+        events.extend(uut.probe(&cpu, &memory).events);
+      }
+
+      assert!(events.contains(&ProbeEvent::MemoryFault {
+        pc: 0x0601,
+        instruction: Some("STA"),
+        fault: MemoryFault::ProtectedWrite(0xE000)
+      }));
+      assert_eq!(memory[0xE000], 0x00);
+    }
+
+    #[test]
+    fn should_return_memory_fault_for_read_of_uninitialized_memory() {
+      let mut memory = Generic64kMem::new();
+      memory.track_uninitialized();
+      memory.insert(0x0600, &[PHA, PLA, LDA_A, 0x00, 0x02]);
+      let mut cpu = CPU::new_nmos();
+      cpu.program_counter = 0x0600;
+      cpu.addr = Address::new();
+
+      let mut uut = Debugger::new();
+
+      let mut faults = Vec::new();
+      for _ in 0..11 {
+        cpu.tick(&mut memory);
+        faults.extend(
+          uut
+            .probe(&cpu, &memory)
+            .events
+            .into_iter()
+            .filter(|event| matches!(event, ProbeEvent::MemoryFault { .. })),
+        );
+      }
+
+      assert_eq!(
+        faults,
+        vec![ProbeEvent::MemoryFault {
+          pc: 0x0602,
+          instruction: Some("LDA"),
+          fault: MemoryFault::UninitializedRead(0x0200)
+        }]
+      );
+    }
+
     #[test]
     fn should_return_registers_and_processor_status() {
       let mut memory = MemoryMock::new(&[LDA_A, 0x04, 0x00, LDX_A, 0x00, 0x00, LDY_A, 0x01, 0x00]);
@@ -758,4 +1017,327 @@ mod tests {
       assert_eq!(last_instruction.target_symbol, Some(String::from(".START")));
     }
   }
+
+  #[cfg(test)]
+  mod breakpoints {
+    use crate::{
+      cpu::{
+        CPU,
+        debugger::{Debugger, ProbeEvent},
+        instructions::{INX_IM, NOP},
+        tests::MemoryMock,
+        utils::execute_next_instruction,
+      },
+      memory::Memory,
+    };
+
+    fn breakpoint_hits(events: &[ProbeEvent]) -> Vec<&ProbeEvent> {
+      events
+        .iter()
+        .filter(|event| matches!(event, ProbeEvent::BreakpointHit { .. }))
+        .collect()
+    }
+
+    // Events of the check before the first instruction, then of every instruction, so the
+    // events at index n are the ones before the instruction at n executes.
+    fn run(uut: &mut Debugger, instructions: usize) -> (CPU, Vec<Vec<ProbeEvent>>) {
+      let mut memory = MemoryMock::new(&[NOP, INX_IM, NOP, INX_IM, NOP, NOP]);
+      let mut cpu = CPU::new_nmos();
+      cpu.program_counter = 0x00;
+      let mut events = vec![uut.check_breakpoints(&cpu)];
+      for _ in 0..instructions {
+        events.push(execute_next_instruction(
+          &mut cpu,
+          &mut memory as &mut dyn Memory,
+          Some(uut),
+        ));
+      }
+      (cpu, events)
+    }
+
+    #[test]
+    fn should_fire_before_instruction_at_address_executes() {
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x01..=0x01);
+
+      let (cpu, events) = run(&mut uut, 2);
+
+      assert!(breakpoint_hits(&events[0]).is_empty());
+      assert_eq!(
+        breakpoint_hits(&events[1]),
+        [&ProbeEvent::BreakpointHit { id, pc: 0x01 }]
+      );
+      assert!(breakpoint_hits(&events[2]).is_empty());
+      assert_eq!(uut.breakpoint(id).unwrap().hit_count, 1);
+      assert_eq!(cpu.index_register_x, 1);
+    }
+
+    #[test]
+    fn should_fire_for_every_instruction_within_range() {
+      let mut uut = Debugger::new();
+      uut.add_breakpoint(0x02..=0x04);
+
+      let (_, events) = run(&mut uut, 5);
+
+      let fired: Vec<usize> = events
+        .iter()
+        .map(|events| breakpoint_hits(events).len())
+        .collect();
+      assert_eq!(fired, [0, 0, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn should_not_fire_nor_count_hits_when_disabled() {
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x01..=0x01);
+      uut.breakpoint_mut(id).unwrap().enabled = false;
+
+      let (_, events) = run(&mut uut, 2);
+
+      assert!(
+        events
+          .iter()
+          .all(|events| breakpoint_hits(events).is_empty())
+      );
+      assert_eq!(uut.breakpoint(id).unwrap().hit_count, 0);
+    }
+
+    #[test]
+    fn should_skip_ignored_hits() {
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x00..=0xFF);
+      uut.breakpoint_mut(id).unwrap().ignore_count = 2;
+
+      let (_, events) = run(&mut uut, 4);
+
+      let fired: Vec<usize> = events
+        .iter()
+        .map(|events| breakpoint_hits(events).len())
+        .collect();
+      assert_eq!(fired, [0, 0, 1, 1, 1]);
+      let breakpoint = uut.breakpoint(id).unwrap();
+      assert_eq!(breakpoint.hit_count, 5);
+      assert_eq!(breakpoint.ignore_count, 0);
+    }
+
+    #[test]
+    fn should_remove_temporary_breakpoint_once_fired() {
+      let mut uut = Debugger::new();
+      let id = uut.add_temporary_breakpoint(0x00..=0xFF);
+
+      let (_, events) = run(&mut uut, 3);
+
+      assert_eq!(breakpoint_hits(&events[0]).len(), 1);
+      assert!(breakpoint_hits(&events[1]).is_empty());
+      assert!(uut.breakpoint(id).is_none());
+    }
+
+    #[test]
+    fn should_count_hit_once_when_probed_again_between_instructions() {
+      let mut memory = MemoryMock::new(&[NOP, NOP]);
+      let mut cpu = CPU::new_nmos();
+      cpu.program_counter = 0x00;
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x01..=0x01);
+
+      execute_next_instruction(&mut cpu, &mut memory, Some(&mut uut));
+      let result = uut.probe(&cpu, &memory);
+
+      assert!(breakpoint_hits(&result.events).is_empty());
+      assert_eq!(uut.breakpoint(id).unwrap().hit_count, 1);
+    }
+
+    #[test]
+    fn should_fire_at_first_instruction_before_it_runs() {
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x00..=0x00);
+
+      let (cpu, events) = run(&mut uut, 1);
+
+      assert_eq!(
+        breakpoint_hits(&events[0]),
+        [&ProbeEvent::BreakpointHit { id, pc: 0x00 }]
+      );
+      assert!(breakpoint_hits(&events[1]).is_empty());
+      assert_eq!(cpu.program_counter, 0x01);
+    }
+
+    #[test]
+    fn should_remove_breakpoint() {
+      let mut uut = Debugger::new();
+      let id = uut.add_breakpoint(0x01..=0x01);
+      let other = uut.add_breakpoint(0x02..=0x02);
+
+      assert!(uut.remove_breakpoint(id).is_some());
+
+      let ids: Vec<_> = uut.breakpoints().map(|(id, _)| id).collect();
+      assert_eq!(ids, [other]);
+      assert!(uut.remove_breakpoint(id).is_none());
+    }
+  }
+
+  #[cfg(test)]
+  mod watchpoints {
+    use crate::{
+      consts::Word,
+      cpu::{
+        CPU,
+        debugger::{Debugger, ProbeEvent, Symbols, WatchpointHit, watchpoint::WatchKind},
+        utils::execute_next_instruction,
+      },
+      memory::{BusAccessKind, Generic64kMem, bus_monitor::BusMonitor},
+    };
+
+    // LDA $0200, STA $0201, INC $0201, LDA #$00, STA $0201
+    fn run(uut: &mut Debugger) -> Vec<WatchpointHit> {
+      let mut inner = Generic64kMem::new();
+      inner.insert(
+        0x0600,
+        &[
+          0xAD, 0x00, 0x02, 0x8D, 0x01, 0x02, 0xEE, 0x01, 0x02, 0xA9, 0x00, 0x8D, 0x01, 0x02,
+        ],
+      );
+      inner.insert(0x0200, &[0x42, 0x00]);
+      inner.insert(0xFFFC, &[0x00, 0x06]);
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&inner);
+      let mut memory = BusMonitor::new(inner);
+
+      (0..5)
+        .flat_map(|_| execute_next_instruction(&mut cpu, &mut memory, Some(uut)))
+        .filter_map(|event| match event {
+          ProbeEvent::WatchpointHit(hit) => Some(hit),
+          _ => None,
+        })
+        .collect()
+    }
+
+    fn accesses(hits: &[WatchpointHit]) -> Vec<(BusAccessKind, Word, u8, u8)> {
+      hits
+        .iter()
+        .map(|hit| (hit.kind, hit.pc, hit.old, hit.new))
+        .collect()
+    }
+
+    #[test]
+    fn should_report_writer_of_watched_address() {
+      let mut uut = Debugger::new();
+      let id = uut.add_watchpoint(0x0201..=0x0201, WatchKind::Write);
+
+      let hits = run(&mut uut);
+
+      assert_eq!(
+        accesses(&hits),
+        [
+          (BusAccessKind::Write, 0x0603, 0x00, 0x42),
+          // read-modify-write stores the unmodified value first
+          (BusAccessKind::Write, 0x0606, 0x42, 0x42),
+          (BusAccessKind::Write, 0x0606, 0x42, 0x43),
+          (BusAccessKind::Write, 0x060B, 0x43, 0x00),
+        ]
+      );
+      assert_eq!(hits[0].id, id);
+      assert_eq!(hits[0].addr, 0x0201);
+      assert_eq!(hits[0].instruction, Some("STA"));
+      assert_eq!(uut.watchpoint(id).unwrap().hit_count, 4);
+    }
+
+    #[test]
+    fn should_report_only_writes_changing_value() {
+      let mut uut = Debugger::new();
+      uut.add_watchpoint(0x0201..=0x0201, WatchKind::Change);
+
+      let hits = run(&mut uut);
+
+      assert_eq!(
+        accesses(&hits),
+        [
+          (BusAccessKind::Write, 0x0603, 0x00, 0x42),
+          (BusAccessKind::Write, 0x0606, 0x42, 0x43),
+          (BusAccessKind::Write, 0x060B, 0x43, 0x00),
+        ]
+      );
+    }
+
+    #[test]
+    fn should_report_reads_within_range() {
+      let mut uut = Debugger::new();
+      uut.add_watchpoint(0x0200..=0x0201, WatchKind::Read);
+
+      let hits = run(&mut uut);
+
+      assert_eq!(
+        accesses(&hits),
+        [
+          (BusAccessKind::Read, 0x0600, 0x42, 0x42),
+          (BusAccessKind::Read, 0x0606, 0x42, 0x42),
+        ]
+      );
+    }
+
+    #[test]
+    fn should_report_cycle_of_access() {
+      let mut uut = Debugger::new();
+      uut.add_watchpoint(0x0201..=0x0201, WatchKind::Access);
+
+      let hits = run(&mut uut);
+
+      // STA writes in its 4th cycle, INC reads in its 4th and writes in its 5th and 6th,
+      // the last STA writes 2 cycles of LDA later
+      let start = hits[0].cycle;
+      let cycles: Vec<usize> = hits.iter().map(|hit| hit.cycle - start).collect();
+      assert_eq!(cycles, [0, 4, 5, 6, 12]);
+    }
+
+    #[test]
+    fn should_not_report_when_disabled_or_removed() {
+      let mut uut = Debugger::new();
+      let disabled = uut.add_watchpoint(0x0000..=0xFFFF, WatchKind::Access);
+      uut.watchpoint_mut(disabled).unwrap().enabled = false;
+      let removed = uut.add_watchpoint(0x0000..=0xFFFF, WatchKind::Access);
+      uut.remove_watchpoint(removed);
+
+      assert!(run(&mut uut).is_empty());
+      assert_eq!(uut.watchpoint(disabled).unwrap().hit_count, 0);
+      assert_eq!(uut.watchpoints().count(), 1);
+    }
+
+    #[test]
+    fn should_resolve_symbol_of_address_when_probed_with_symbols() {
+      struct Variables;
+      impl Symbols for Variables {
+        fn get(&self, addr: &Word) -> Option<String> {
+          (*addr == 0x0201).then(|| String::from("score"))
+        }
+      }
+
+      let mut inner = Generic64kMem::new();
+      // STA $0201
+      inner.insert(0x0600, &[0x8D, 0x01, 0x02]);
+      inner.insert(0xFFFC, &[0x00, 0x06]);
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&inner);
+      let mut memory = BusMonitor::new(inner);
+      let mut uut = Debugger::new();
+      uut.add_watchpoint(0x0201..=0x0201, WatchKind::Write);
+
+      let mut hits = Vec::new();
+      for _ in 0..4 {
+        cpu.tick(&mut memory);
+        hits.extend(
+          uut
+            .probe_with_symbols(&cpu, &memory, &Variables)
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+              ProbeEvent::WatchpointHit(hit) => Some(hit),
+              _ => None,
+            }),
+        );
+      }
+
+      assert_eq!(hits.len(), 1);
+      assert_eq!(hits[0].symbol.as_deref(), Some("score"));
+    }
+  }
 }
@@ -0,0 +1,45 @@
+use std::ops::RangeInclusive;
+
+use crate::consts::Word;
+
+pub type BreakpointId = usize;
+
+/// Stops execution before an instruction within `range` executes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+  pub range: RangeInclusive<Word>,
+  pub enabled: bool,
+  /// Times an instruction within the range was reached while enabled, ignored hits included.
+  pub hit_count: usize,
+  /// Number of upcoming hits that don't fire.
+  pub ignore_count: usize,
+  /// Removed once it fires.
+  pub temporary: bool,
+}
+
+impl Breakpoint {
+  pub fn new(range: RangeInclusive<Word>) -> Self {
+    Breakpoint {
+      range,
+      enabled: true,
+      hit_count: 0,
+      ignore_count: 0,
+      temporary: false,
+    }
+  }
+
+  // Records reaching `pc` and returns whether the breakpoint fires.
+  pub(super) fn hit(&mut self, pc: Word) -> bool {
+    if !self.enabled || !self.range.contains(&pc) {
+      return false;
+    }
+
+    self.hit_count += 1;
+    if self.ignore_count > 0 {
+      self.ignore_count -= 1;
+      return false;
+    }
+
+    true
+  }
+}
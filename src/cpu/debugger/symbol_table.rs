@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use crate::{consts::Word, cpu::debugger::Symbols};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SymbolTable {
+  names: BTreeMap<Word, String>,
+}
+
+impl SymbolTable {
+  pub fn new() -> Self {
+    SymbolTable {
+      names: BTreeMap::new(),
+    }
+  }
+
+  /// Adds a symbol unless the address is already named; the first name wins.
+  pub fn insert(&mut self, addr: Word, name: &str) {
+    self.names.entry(addr).or_insert_with(|| String::from(name));
+  }
+
+  pub fn address_of(&self, name: &str) -> Option<Word> {
+    self
+      .names
+      .iter()
+      .find(|(_, symbol)| symbol.as_str() == name)
+      .map(|(addr, _)| *addr)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (Word, &str)> {
+    self.names.iter().map(|(addr, name)| (*addr, name.as_str()))
+  }
+
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+}
+
+impl Symbols for SymbolTable {
+  fn get(&self, addr: &Word) -> Option<String> {
+    self.names.get(addr).cloned()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod symbol_table {
+    use crate::cpu::debugger::{Symbols, symbol_table::SymbolTable};
+
+    #[test]
+    fn should_keep_first_name_inserted_for_an_address() {
+      let mut uut = SymbolTable::new();
+
+      uut.insert(0x0600, "main");
+      uut.insert(0x0600, ".text");
+
+      assert_eq!(uut.get(&0x0600), Some(String::from("main")));
+      assert_eq!(uut.address_of("main"), Some(0x0600));
+      assert_eq!(uut.address_of(".text"), None);
+    }
+  }
+}
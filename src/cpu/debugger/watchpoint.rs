@@ -0,0 +1,59 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::Word,
+  memory::{BusAccess, BusAccessKind},
+};
+
+pub type WatchpointId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchKind {
+  Read,
+  Write,
+  /// Writes storing a value different from the previous one.
+  Change,
+  /// Reads and writes.
+  Access,
+}
+
+/// Reports data accesses of the CPU within `range`. Accesses are only seen through memories
+/// monitoring the bus, like `BusMonitor`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watchpoint {
+  pub range: RangeInclusive<Word>,
+  pub kind: WatchKind,
+  pub enabled: bool,
+  pub hit_count: usize,
+}
+
+impl Watchpoint {
+  pub fn new(range: RangeInclusive<Word>, kind: WatchKind) -> Self {
+    Watchpoint {
+      range,
+      kind,
+      enabled: true,
+      hit_count: 0,
+    }
+  }
+
+  // Records `access` and returns whether the watchpoint fires.
+  pub(super) fn hit(&mut self, access: &BusAccess) -> bool {
+    if !self.enabled || !self.range.contains(&access.addr) {
+      return false;
+    }
+
+    let fires = match (self.kind, access.kind) {
+      (WatchKind::Access, _)
+      | (WatchKind::Read, BusAccessKind::Read)
+      | (WatchKind::Write, BusAccessKind::Write) => true,
+      (WatchKind::Change, BusAccessKind::Write) => access.old != access.new,
+      _ => false,
+    };
+    if fires {
+      self.hit_count += 1;
+    }
+
+    fires
+  }
+}
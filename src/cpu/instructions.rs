@@ -1,13 +1,16 @@
 #![allow(dead_code)]
 
 use paste::paste;
-use phf::phf_map;
 
 use crate::consts::Byte;
 use crate::cpu::CPU;
-use crate::cpu::tasks::Tasks;
+use crate::cpu::tasks::{
+  modify_memory::ModifyMemoryTasks, modify_register::ModifyRegisterTasks, tasks_enum,
+  transfer_register::TransferRegistersTasks,
+};
 
 use self::arithmetic::*;
+pub(super) use self::arithmetic::{adc, sbc};
 use self::branches::*;
 use self::inc_and_decrements::*;
 use self::jumps_and_calls::*;
@@ -18,9 +21,35 @@ use self::shifts::*;
 use self::stack_operations::*;
 use self::status_flag_changes::*;
 use self::system_functions::*;
+pub(super) use self::system_functions::{BrkTasks, Interrupt};
 
-type OpcodeHandler = fn(&mut CPU) -> Box<dyn Tasks>;
+tasks_enum! {
+  #[derive(Clone)]
+  pub enum InstructionTasks {
+    ModifyMemory(ModifyMemoryTasks),
+    ModifyRegister(ModifyRegisterTasks),
+    TransferRegisters(TransferRegistersTasks),
+    Compare(CompareTasks),
+    OperationsWithCarry(OperationsWithCarryTasks),
+    Branch(BranchTasks),
+    Jsr(JsrTasks),
+    Rts(RtsTasks),
+    Jmp(JmpTasks),
+    Load(LoadTasks),
+    Store(StoreTasks),
+    Logical(LogicalTasks),
+    PushRegister(PushRegisterTasks),
+    PullRegister(PullRegisterTasks),
+    ChangeStatusFlag(ChangeStatusFlagTasks),
+    Nop(NopTasks),
+    Brk(BrkTasks),
+    Rti(RtiTasks),
+  }
+}
+
+type OpcodeHandler = fn(&mut CPU) -> InstructionTasks;
 
+#[derive(Clone, Copy)]
 pub struct Instruction {
   pub handler: OpcodeHandler,
   pub name: &'static str,
@@ -34,17 +63,19 @@ macro_rules! instructions {
       )+
     }
 
-    // this is required becasue phf currently cannot evalute consts as keys
-    pub static INSTRUCTIONS: phf::Map<Byte, Instruction> = phf_map!{
+    /// Instructions indexed by opcode.
+    pub static INSTRUCTIONS: [Option<Instruction>; 256] = {
+      let mut instructions = [None; 256];
       $(
-        $opcode => Instruction { handler: $handler, name: $display }
-      ),+
+        assert!(instructions[$opcode as usize].is_none(), "duplicate opcode");
+        instructions[$opcode as usize] = Some(Instruction { handler: $handler, name: $display });
+      )+
+      instructions
     };
   };
 }
 
 instructions! {
-  // u8 suffix is required for phf
   0x69u8 => (adc_im, "ADC"),
   0x65u8 => (adc_zp, "ADC"),
   0x75u8 => (adc_zpx, "ADC"),
@@ -1,33 +1,41 @@
+use strum::FromRepr;
+
 use crate::{
   consts::Byte,
   cpu::{
     AddressingMode, CPU, Registers, Tasks,
     addressing::{
-      AddressingTasks, OffsetVariant,
+      AddressingModeTasks, AddressingTasks, OffsetVariant,
       absolute::{AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks, AccessVariant},
       indirect::{IndexIndirectXAddressingTasks, IndirectIndexYAddressingTasks},
       zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks},
     },
-    tasks::read_memory::{ImmediateReadMemoryTasks, ReadMemoryTasks},
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+    tasks::read_memory::{AddressingReadMemoryTasks, ImmediateReadMemoryTasks, ReadMemoryTasks},
   },
   memory::Memory,
 };
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, FromRepr)]
+#[repr(u8)]
 enum CompareTasksSteps {
   Addressing,
   MemoryAccess,
 }
 
-struct CompareTasks {
+enum_state_field!(CompareTasksSteps);
+
+#[derive(Clone)]
+pub struct CompareTasks {
   done: bool,
   step: CompareTasksSteps,
-  addressing_tasks: Box<dyn AddressingTasks>,
+  addressing_tasks: AddressingModeTasks,
   register: Registers,
 }
 
 impl CompareTasks {
-  pub fn new(addressing_tasks: Box<dyn AddressingTasks>, register: Registers) -> Self {
+  pub fn new(addressing_tasks: AddressingModeTasks, register: Registers) -> Self {
     CompareTasks {
       addressing_tasks,
       step: CompareTasksSteps::Addressing,
@@ -68,113 +76,110 @@ impl Tasks for CompareTasks {
 
     true
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    state.write(&self.step);
+    self.addressing_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.step = state.read()?;
+    self.addressing_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
-pub fn cmp_im(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ImmediateReadMemoryTasks::new()),
+pub fn cmp_im(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    ImmediateReadMemoryTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
+pub fn cmp_zp(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    ZeroPageAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_x()),
+pub fn cmp_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    ZeroPageOffsetAddressingTasks::new_offset_by_x().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
+pub fn cmp_a(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    AbsoluteAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(AbsoluteOffsetAddressingTasks::new(
-      OffsetVariant::X,
-      AccessVariant::Read,
-    )),
+pub fn cmp_ax(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Read).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_ay(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(AbsoluteOffsetAddressingTasks::new(
-      OffsetVariant::Y,
-      AccessVariant::Read,
-    )),
+pub fn cmp_ay(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::Y, AccessVariant::Read).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_inx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(IndexIndirectXAddressingTasks::new()),
+pub fn cmp_inx(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    IndexIndirectXAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cmp_iny(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(IndirectIndexYAddressingTasks::new(AccessVariant::Read)),
+pub fn cmp_iny(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(
+    IndirectIndexYAddressingTasks::new(AccessVariant::Read).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn cpx_im(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ImmediateReadMemoryTasks::new()),
-    Registers::IndexX,
-  ))
+pub fn cpx_im(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(ImmediateReadMemoryTasks::new().into(), Registers::IndexX).into()
 }
 
-pub fn cpx_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
-    Registers::IndexX,
-  ))
+pub fn cpx_zp(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(ZeroPageAddressingTasks::new().into(), Registers::IndexX).into()
 }
 
-pub fn cpx_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
-    Registers::IndexX,
-  ))
+pub fn cpx_a(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(AbsoluteAddressingTasks::new().into(), Registers::IndexX).into()
 }
 
-pub fn cpy_im(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ImmediateReadMemoryTasks::new()),
-    Registers::IndexY,
-  ))
+pub fn cpy_im(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(ImmediateReadMemoryTasks::new().into(), Registers::IndexY).into()
 }
 
-pub fn cpy_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
-    Registers::IndexY,
-  ))
+pub fn cpy_zp(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(ZeroPageAddressingTasks::new().into(), Registers::IndexY).into()
 }
 
-pub fn cpy_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(CompareTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
-    Registers::IndexY,
-  ))
+pub fn cpy_a(_cpu: &mut CPU) -> InstructionTasks {
+  CompareTasks::new(AbsoluteAddressingTasks::new().into(), Registers::IndexY).into()
 }
 
-fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool) {
+pub fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool) {
   let (val_with_carry, val_carry) = val.overflowing_add(carry as u8);
   let (result, acc_carry) = acc.overflowing_add(val_with_carry);
   // if a sign (0x80) of a result differs from signs of both inputs
@@ -183,19 +188,20 @@ fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool) {
   (result, acc_carry | val_carry, overflow)
 }
 
-fn sbc(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool) {
+pub fn sbc(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool) {
   adc(0xFF - val, acc, carry)
 }
 
-struct OperationsWithCarryTasks {
+#[derive(Clone)]
+pub struct OperationsWithCarryTasks {
   done: bool,
-  read_memory_tasks: Box<dyn ReadMemoryTasks>,
+  read_memory_tasks: AddressingReadMemoryTasks,
   op: fn(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool),
 }
 
 impl OperationsWithCarryTasks {
   pub fn new(
-    read_memory_tasks: Box<dyn ReadMemoryTasks>,
+    read_memory_tasks: AddressingReadMemoryTasks,
     op: fn(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool),
   ) -> Self {
     OperationsWithCarryTasks {
@@ -236,78 +242,89 @@ impl Tasks for OperationsWithCarryTasks {
 
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    self.read_memory_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.read_memory_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
 pub fn operations_with_carry(
   cpu: &mut CPU,
   addr_mode: AddressingMode,
   op: fn(val: Byte, acc: Byte, carry: bool) -> (Byte, bool, bool),
-) -> Box<dyn Tasks> {
+) -> InstructionTasks {
   let read_memory_tasks = cpu.read_memory(addr_mode);
-  Box::new(OperationsWithCarryTasks::new(read_memory_tasks, op))
+  OperationsWithCarryTasks::new(read_memory_tasks, op).into()
 }
 
-pub fn adc_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_im(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::Immediate, adc)
 }
 
-pub fn adc_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_zp(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::ZeroPage, adc)
 }
 
-pub fn adc_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_zpx(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::ZeroPageX, adc)
 }
 
-pub fn adc_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_a(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::Absolute, adc)
 }
 
-pub fn adc_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_ax(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::AbsoluteX, adc)
 }
 
-pub fn adc_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_ay(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::AbsoluteY, adc)
 }
 
-pub fn adc_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_inx(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::IndexIndirectX, adc)
 }
 
-pub fn adc_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn adc_iny(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::IndirectIndexY, adc)
 }
 
-pub fn sbc_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_im(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::Immediate, sbc)
 }
 
-pub fn sbc_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_zp(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::ZeroPage, sbc)
 }
 
-pub fn sbc_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_zpx(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::ZeroPageX, sbc)
 }
 
-pub fn sbc_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_a(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::Absolute, sbc)
 }
 
-pub fn sbc_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_ax(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::AbsoluteX, sbc)
 }
 
-pub fn sbc_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_ay(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::AbsoluteY, sbc)
 }
 
-pub fn sbc_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_inx(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::IndexIndirectX, sbc)
 }
 
-pub fn sbc_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sbc_iny(cpu: &mut CPU) -> InstructionTasks {
   operations_with_carry(cpu, AddressingMode::IndirectIndexY, sbc)
 }
 
@@ -330,7 +347,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -344,7 +361,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -367,7 +384,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -381,7 +398,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -405,7 +422,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -420,7 +437,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -443,7 +460,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -457,7 +474,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -489,7 +506,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -504,7 +521,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -526,7 +543,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -558,7 +575,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -573,7 +590,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -595,7 +612,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -637,7 +654,7 @@ mod cmp {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cmp_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -660,7 +677,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -681,7 +698,7 @@ mod cmp {
       cpu.cycle = 0;
 
       let mut tasks = cmp_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -708,7 +725,7 @@ mod cpy {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpy_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -722,7 +739,7 @@ mod cpy {
       cpu.cycle = 0;
 
       let mut tasks = cpy_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -746,7 +763,7 @@ mod cpy {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpy_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -760,7 +777,7 @@ mod cpy {
       cpu.cycle = 0;
 
       let mut tasks = cpy_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -784,7 +801,7 @@ mod cpy {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpy_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -798,7 +815,7 @@ mod cpy {
       cpu.cycle = 0;
 
       let mut tasks = cpy_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -825,7 +842,7 @@ mod cpx {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpx_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -839,7 +856,7 @@ mod cpx {
       cpu.cycle = 0;
 
       let mut tasks = cpx_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -863,7 +880,7 @@ mod cpx {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpx_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -877,7 +894,7 @@ mod cpx {
       cpu.cycle = 0;
 
       let mut tasks = cpx_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -901,7 +918,7 @@ mod cpx {
       assert_eq!(cpu.processor_status, 0b00100000);
 
       let mut tasks = cpx_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -915,7 +932,7 @@ mod cpx {
       cpu.cycle = 0;
 
       let mut tasks = cpx_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1043,7 +1060,7 @@ mod adc {
       cpu.program_counter = 0x00;
 
       let mut tasks = adc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1058,7 +1075,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1072,7 +1089,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1100,7 +1117,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1115,7 +1132,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1128,7 +1145,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -1157,7 +1174,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1173,7 +1190,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1188,7 +1205,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1216,7 +1233,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1231,7 +1248,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1245,7 +1262,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1278,7 +1295,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1294,7 +1311,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1309,7 +1326,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1329,7 +1346,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1362,7 +1379,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1378,7 +1395,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1393,7 +1410,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1413,7 +1430,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1454,7 +1471,7 @@ mod adc {
       cpu.accumulator = 0x02;
 
       let mut tasks = adc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1477,7 +1494,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1500,7 +1517,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1521,7 +1538,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1560,7 +1577,7 @@ mod adc {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = adc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x05);
     }
@@ -1583,7 +1600,7 @@ mod adc {
       cpu.processor_status = ProcessorStatus::from(0b00100000);
 
       let mut tasks = adc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1605,7 +1622,7 @@ mod adc {
       cpu.cycle = 0;
 
       let mut tasks = adc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1736,7 +1753,7 @@ mod sbc {
       cpu.program_counter = 0x00;
 
       let mut tasks = sbc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -1751,7 +1768,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1766,7 +1783,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1795,7 +1812,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -1810,7 +1827,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1825,7 +1842,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -1855,7 +1872,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -1871,7 +1888,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1886,7 +1903,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1915,7 +1932,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -1930,7 +1947,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -1945,7 +1962,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1979,7 +1996,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -1995,7 +2012,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -2011,7 +2028,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -2032,7 +2049,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -2066,7 +2083,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -2082,7 +2099,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -2098,7 +2115,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -2119,7 +2136,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -2161,7 +2178,7 @@ mod sbc {
       cpu.accumulator = 0x50;
 
       let mut tasks = sbc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -2184,7 +2201,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -2208,7 +2225,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -2230,7 +2247,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -2270,7 +2287,7 @@ mod sbc {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = sbc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x20);
     }
@@ -2293,7 +2310,7 @@ mod sbc {
       cpu.processor_status = ProcessorStatus::from(0b01100000);
 
       let mut tasks = sbc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100001);
     }
@@ -2316,7 +2333,7 @@ mod sbc {
       cpu.cycle = 0;
 
       let mut tasks = sbc_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1,10 +1,17 @@
+use strum::FromRepr;
+
 use crate::{
   consts::{Byte, Word},
-  cpu::{CPU, Tasks},
+  cpu::{
+    CPU, Tasks,
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+  },
   memory::Memory,
 };
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum BranchStep {
   ConditionExecution,
   OffsetProgramCounterLo,
@@ -12,7 +19,10 @@ enum BranchStep {
   Done,
 }
 
-struct BranchTasks {
+enum_state_field!(BranchStep);
+
+#[derive(Clone)]
+pub struct BranchTasks {
   condition: fn(&CPU) -> bool,
   step: BranchStep,
 }
@@ -108,78 +118,71 @@ impl Tasks for BranchTasks {
       BranchStep::Done => true,
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn bcc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bcc(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    !cpu.processor_status.get_carry_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { !cpu.processor_status.get_carry_flag() }).into()
 }
 
-pub fn bcs(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bcs(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    cpu.processor_status.get_carry_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { cpu.processor_status.get_carry_flag() }).into()
 }
 
-pub fn beq(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn beq(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    cpu.processor_status.get_zero_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { cpu.processor_status.get_zero_flag() }).into()
 }
 
-pub fn bmi(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bmi(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    cpu.processor_status.get_negative_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { cpu.processor_status.get_negative_flag() }).into()
 }
 
-pub fn bne(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bne(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    !cpu.processor_status.get_zero_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { !cpu.processor_status.get_zero_flag() }).into()
 }
 
-pub fn bpl(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bpl(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    !cpu.processor_status.get_negative_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { !cpu.processor_status.get_negative_flag() }).into()
 }
 
-pub fn bvs(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bvs(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    cpu.processor_status.get_overflow_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { cpu.processor_status.get_overflow_flag() }).into()
 }
 
-pub fn bvc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn bvc(cpu: &mut CPU) -> InstructionTasks {
   cpu
     .addr
     .reset(crate::cpu::addressing::AddressingMode::Relative);
-  Box::new(BranchTasks::new(|cpu: &CPU| -> bool {
-    !cpu.processor_status.get_overflow_flag()
-  }))
+  BranchTasks::new(|cpu: &CPU| -> bool { !cpu.processor_status.get_overflow_flag() }).into()
 }
 
 #[cfg(test)]
@@ -190,7 +193,6 @@ mod common_branching_tasks {
     cpu::{
       CPU,
       instructions::branches::BranchTasks,
-      tasks::Tasks,
       tests::{MemoryMock, run_tasks},
     },
   };
@@ -202,8 +204,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x0000;
 
     let condition: fn(&CPU) -> bool = |_| false;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
     assert!(cpu.addr.done);
@@ -217,8 +219,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x00;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -233,8 +235,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x02;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x00);
   }
@@ -249,8 +251,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x00FE;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0103);
   }
@@ -265,8 +267,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x00;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0xFFFE);
   }
@@ -279,8 +281,8 @@ mod common_branching_tasks {
     cpu.cycle = 0;
 
     let condition: fn(&CPU) -> bool = |_| false;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -294,8 +296,8 @@ mod common_branching_tasks {
     cpu.cycle = 0;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -311,8 +313,8 @@ mod common_branching_tasks {
     cpu.cycle = 0;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -325,8 +327,8 @@ mod common_branching_tasks {
     cpu.program_counter = 0x00;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.addr.indirect(), Some(0x0003));
     assert_eq!(cpu.addr.value(), Some(0x0004));
@@ -343,8 +345,8 @@ mod common_branching_tasks {
     cpu.cycle = 0;
 
     let condition: fn(&CPU) -> bool = |_| true;
-    let mut tasks = Box::new(BranchTasks::new(condition)) as Box<dyn Tasks>;
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    let mut tasks = BranchTasks::new(condition);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.addr.indirect(), Some(0x0004));
     assert_eq!(cpu.addr.value(), Some(0x0103));
@@ -371,7 +373,7 @@ mod bcc {
     cpu.program_counter = 0x00;
 
     let mut tasks = bcc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -385,7 +387,7 @@ mod bcc {
     cpu.program_counter = 0x00;
 
     let mut tasks = bcc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -411,7 +413,7 @@ mod bcs {
     cpu.program_counter = 0x00;
 
     let mut tasks = bcs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -425,7 +427,7 @@ mod bcs {
     cpu.program_counter = 0x00;
 
     let mut tasks = bcs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -451,7 +453,7 @@ mod beq {
     cpu.program_counter = 0x00;
 
     let mut tasks = beq(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -465,7 +467,7 @@ mod beq {
     cpu.program_counter = 0x00;
 
     let mut tasks = beq(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -491,7 +493,7 @@ mod bmi {
     cpu.program_counter = 0x00;
 
     let mut tasks = bmi(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -505,7 +507,7 @@ mod bmi {
     cpu.program_counter = 0x00;
 
     let mut tasks = bmi(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -531,7 +533,7 @@ mod bne {
     cpu.program_counter = 0x00;
 
     let mut tasks = bne(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -545,7 +547,7 @@ mod bne {
     cpu.program_counter = 0x00;
 
     let mut tasks = bne(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -571,7 +573,7 @@ mod bpl {
     cpu.program_counter = 0x00;
 
     let mut tasks = bpl(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -585,7 +587,7 @@ mod bpl {
     cpu.program_counter = 0x00;
 
     let mut tasks = bpl(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -611,7 +613,7 @@ mod bvc {
     cpu.program_counter = 0x00;
 
     let mut tasks = bvc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -625,7 +627,7 @@ mod bvc {
     cpu.program_counter = 0x00;
 
     let mut tasks = bvc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
@@ -651,7 +653,7 @@ mod bvs {
     cpu.program_counter = 0x00;
 
     let mut tasks = bvs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0001);
   }
@@ -665,7 +667,7 @@ mod bvs {
     cpu.program_counter = 0x00;
 
     let mut tasks = bvs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x0004);
   }
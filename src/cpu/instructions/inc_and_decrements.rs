@@ -1,91 +1,82 @@
 use crate::cpu::{
-  CPU, Registers, Tasks,
+  CPU, Registers,
   addressing::{
     OffsetVariant,
     absolute::{AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks, AccessVariant},
     zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks},
   },
+  instructions::InstructionTasks,
   tasks::{modify_memory::ModifyMemoryTasks, modify_register::ModifyRegisterTasks},
 };
 
-fn decrement_register(_cpu: &mut CPU, register: Registers) -> Box<dyn Tasks> {
+fn decrement_register(_cpu: &mut CPU, register: Registers) -> InstructionTasks {
   match register {
-    Registers::IndexX | Registers::IndexY => Box::new(ModifyRegisterTasks::new_dec(register)),
+    Registers::IndexX | Registers::IndexY => ModifyRegisterTasks::new_dec(register).into(),
     _ => panic!("decrement_register used with incorrect register"),
   }
 }
 
-pub fn dec_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_dec(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn dec_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_dec(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn dec_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_dec(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn dec_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_dec(ZeroPageOffsetAddressingTasks::new_offset_by_x().into()).into()
 }
 
-pub fn dec_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_dec(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn dec_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_dec(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn dec_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_dec(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn dec_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_dec(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
-pub fn dex_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn dex_im(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   decrement_register(cpu, Registers::IndexX)
 }
 
-pub fn dey_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn dey_im(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   decrement_register(cpu, Registers::IndexY)
 }
 
-fn increment_register(_cpu: &mut CPU, register: Registers) -> Box<dyn Tasks> {
+fn increment_register(_cpu: &mut CPU, register: Registers) -> InstructionTasks {
   match register {
-    Registers::IndexX | Registers::IndexY => Box::new(ModifyRegisterTasks::new_inc(register)),
+    Registers::IndexX | Registers::IndexY => ModifyRegisterTasks::new_inc(register).into(),
     _ => panic!("increment_register used with incorrect register"),
   }
 }
 
-pub fn inc_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_inc(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn inc_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_inc(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn inc_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_inc(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn inc_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_inc(ZeroPageOffsetAddressingTasks::new_offset_by_x().into()).into()
 }
 
-pub fn inc_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_inc(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn inc_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_inc(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn inc_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_inc(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn inc_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_inc(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
-pub fn inx_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn inx_im(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   increment_register(cpu, Registers::IndexX)
 }
 
-pub fn iny_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn iny_im(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   increment_register(cpu, Registers::IndexY)
 }
@@ -105,7 +96,7 @@ mod inx_im {
     cpu.index_register_x = 0x02;
 
     let mut tasks = inx_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_x, 0x03);
   }
@@ -118,7 +109,7 @@ mod inx_im {
     cpu.cycle = 0;
 
     let mut tasks = inx_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -130,7 +121,7 @@ mod inx_im {
     cpu.index_register_x = 0xFF;
 
     let mut tasks = inx_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -151,7 +142,7 @@ mod iny_im {
     cpu.index_register_y = 0x02;
 
     let mut tasks = iny_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_y, 0x03);
   }
@@ -164,7 +155,7 @@ mod iny_im {
     cpu.cycle = 0;
 
     let mut tasks = iny_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -176,7 +167,7 @@ mod iny_im {
     cpu.index_register_y = 0xFF;
 
     let mut tasks = iny_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -200,7 +191,7 @@ mod inc_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = inc_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x03);
   }
@@ -213,7 +204,7 @@ mod inc_zp {
     cpu.cycle = 0;
 
     let mut tasks = inc_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 4);
   }
@@ -226,7 +217,7 @@ mod inc_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = inc_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -252,7 +243,7 @@ mod inc_zpx {
     cpu.index_register_x = 0x02;
 
     let mut tasks = inc_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR_SUM_X as Word], 0x0A);
   }
@@ -266,7 +257,7 @@ mod inc_zpx {
     cpu.cycle = 0;
 
     let mut tasks = inc_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -280,7 +271,7 @@ mod inc_zpx {
     cpu.index_register_x = 0x02;
 
     let mut tasks = inc_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -306,7 +297,7 @@ mod inc_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = inc_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR as Word], 0x0A);
   }
@@ -319,7 +310,7 @@ mod inc_a {
     cpu.cycle = 0;
 
     let mut tasks = inc_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -332,7 +323,7 @@ mod inc_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = inc_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -360,7 +351,7 @@ mod inc_ax {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = inc_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR_OFFSET_BY_X], 0x0A);
   }
@@ -374,7 +365,7 @@ mod inc_ax {
     cpu.cycle = 0;
 
     let mut tasks = inc_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 6);
   }
@@ -388,7 +379,7 @@ mod inc_ax {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = inc_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -409,7 +400,7 @@ mod dex_im {
     cpu.index_register_x = 0x02;
 
     let mut tasks = dex_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_x, 0x01);
   }
@@ -422,7 +413,7 @@ mod dex_im {
     cpu.cycle = 0;
 
     let mut tasks = dex_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -434,7 +425,7 @@ mod dex_im {
     cpu.index_register_x = 0x01;
 
     let mut tasks = dex_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -455,7 +446,7 @@ mod dey_im {
     cpu.index_register_y = 0x02;
 
     let mut tasks = dey_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_y, 0x01);
   }
@@ -468,7 +459,7 @@ mod dey_im {
     cpu.cycle = 0;
 
     let mut tasks = dey_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -480,7 +471,7 @@ mod dey_im {
     cpu.index_register_y = 0x01;
 
     let mut tasks = dey_im(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -504,7 +495,7 @@ mod dec_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = dec_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x01);
   }
@@ -517,7 +508,7 @@ mod dec_zp {
     cpu.cycle = 0;
 
     let mut tasks = dec_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 4);
   }
@@ -530,7 +521,7 @@ mod dec_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = dec_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -556,7 +547,7 @@ mod dec_zpx {
     cpu.index_register_x = 0x02;
 
     let mut tasks = dec_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR_SUM_X as Word], 0x08);
   }
@@ -570,7 +561,7 @@ mod dec_zpx {
     cpu.cycle = 0;
 
     let mut tasks = dec_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -584,7 +575,7 @@ mod dec_zpx {
     cpu.index_register_x = 0x02;
 
     let mut tasks = dec_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -610,7 +601,7 @@ mod dec_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = dec_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR as Word], 0x08);
   }
@@ -623,7 +614,7 @@ mod dec_a {
     cpu.cycle = 0;
 
     let mut tasks = dec_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -636,7 +627,7 @@ mod dec_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = dec_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
@@ -664,7 +655,7 @@ mod dec_ax {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = dec_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR_OFFSET_BY_X], 0x08);
   }
@@ -678,7 +669,7 @@ mod dec_ax {
     cpu.cycle = 0;
 
     let mut tasks = dec_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 6);
   }
@@ -692,7 +683,7 @@ mod dec_ax {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = dec_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b00100010);
   }
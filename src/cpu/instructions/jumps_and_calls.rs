@@ -1,13 +1,20 @@
+use strum::FromRepr;
+
 use crate::{
   consts::{Byte, Word},
   cpu::{
     CPU, ChipVariant, Tasks,
-    addressing::{absolute::AbsoluteAddressingTasks, indirect::IndirectAddressingTasks},
+    addressing::{
+      AddressingModeTasks, absolute::AbsoluteAddressingTasks, indirect::IndirectAddressingTasks,
+    },
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
   },
   memory::Memory,
 };
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum JsrSteps {
   LoAddressFetch,
   FetchStack,
@@ -17,7 +24,10 @@ enum JsrSteps {
   Done,
 }
 
-struct JsrTasks {
+enum_state_field!(JsrSteps);
+
+#[derive(Clone)]
+pub struct JsrTasks {
   step: JsrSteps,
   lo_addr: Option<Byte>,
 }
@@ -46,7 +56,7 @@ impl Tasks for JsrTasks {
         false
       }
       JsrSteps::FetchStack => {
-        _ = memory[cpu.get_stack_ptr_address()]; // dummy fetch
+        memory.dummy_read(cpu.get_stack_ptr_address()); // dummy fetch
         self.step = JsrSteps::PushProgramCounterHi;
         false
       }
@@ -85,13 +95,25 @@ impl Tasks for JsrTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+    state.write(&self.lo_addr);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    self.lo_addr = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn jsr_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(JsrTasks::new())
+pub fn jsr_a(_cpu: &mut CPU) -> InstructionTasks {
+  JsrTasks::new().into()
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum RtsSteps {
   DummyFetch,
   PreDecrementStackPointer,
@@ -101,7 +123,10 @@ enum RtsSteps {
   Done,
 }
 
-struct RtsTasks {
+enum_state_field!(RtsSteps);
+
+#[derive(Clone)]
+pub struct RtsTasks {
   step: RtsSteps,
 }
 
@@ -126,7 +151,7 @@ impl Tasks for RtsTasks {
         false
       }
       RtsSteps::PreDecrementStackPointer => {
-        _ = memory[cpu.get_stack_ptr_address()]; // dummy read
+        memory.dummy_read(cpu.get_stack_ptr_address()); // dummy read
         cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
         self.step = RtsSteps::PopProgramCounterLo;
         false
@@ -157,19 +182,29 @@ impl Tasks for RtsTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn rts(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn rts(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(RtsTasks::new())
+  RtsTasks::new().into()
 }
 
+#[derive(Clone)]
 pub struct JmpTasks {
-  addressing_tasks: Box<dyn Tasks>,
+  addressing_tasks: AddressingModeTasks,
 }
 
 impl JmpTasks {
-  fn new(addressing_tasks: Box<dyn Tasks>) -> Self {
+  fn new(addressing_tasks: AddressingModeTasks) -> Self {
     JmpTasks { addressing_tasks }
   }
 }
@@ -195,19 +230,28 @@ impl Tasks for JmpTasks {
 
     done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    self.addressing_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.addressing_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
-pub fn jmp_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(JmpTasks::new(Box::new(AbsoluteAddressingTasks::new())))
+pub fn jmp_a(_cpu: &mut CPU) -> InstructionTasks {
+  JmpTasks::new(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn jmp_in(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn jmp_in(cpu: &mut CPU) -> InstructionTasks {
   let addr_tasks = if cpu.chip_variant == ChipVariant::NMOS {
-    Box::new(IndirectAddressingTasks::new_incorrect_addressing())
+    IndirectAddressingTasks::new_incorrect_addressing().into()
   } else {
-    Box::new(IndirectAddressingTasks::new_fixed_addressing())
+    IndirectAddressingTasks::new_fixed_addressing().into()
   };
-  Box::new(JmpTasks::new(addr_tasks))
+  JmpTasks::new(addr_tasks).into()
 }
 
 #[cfg(test)]
@@ -227,7 +271,7 @@ mod jsr_a {
     cpu.stack_pointer = 0xFF;
 
     let mut tasks = jsr_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x5144);
   }
@@ -240,7 +284,7 @@ mod jsr_a {
     cpu.stack_pointer = 0xFF;
 
     let mut tasks = jsr_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[0x01FF], 0x00);
     assert_eq!(memory[0x01FE], 0x01);
@@ -254,7 +298,7 @@ mod jsr_a {
     cpu.stack_pointer = 0xFF;
 
     let mut tasks = jsr_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.stack_pointer, 0xFD);
   }
@@ -267,7 +311,7 @@ mod jsr_a {
     cpu.cycle = 0;
 
     let mut tasks = jsr_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -280,7 +324,7 @@ mod jsr_a {
     cpu.cycle = 0;
 
     let mut tasks = jsr_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.addr.value(), Some(0x5144));
     assert!(cpu.addr.done);
@@ -306,7 +350,7 @@ mod rts {
     cpu.stack_pointer = 0xFD;
 
     let mut tasks = rts(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x4452);
   }
@@ -321,7 +365,7 @@ mod rts {
     cpu.stack_pointer = 0xFD;
 
     let mut tasks = rts(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.stack_pointer, 0xFF);
   }
@@ -337,7 +381,7 @@ mod rts {
     cpu.cycle = 0;
 
     let mut tasks = rts(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -359,7 +403,7 @@ mod jmp_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = jmp_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.program_counter, 0x5144);
   }
@@ -372,7 +416,7 @@ mod jmp_a {
     cpu.cycle = 0;
 
     let mut tasks = jmp_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -396,7 +440,7 @@ mod jmp_in {
       cpu.program_counter = 0x00;
 
       let mut tasks = jmp_in(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.program_counter, 0x0001);
     }
@@ -419,7 +463,7 @@ mod jmp_in {
       cpu.cycle = 0;
 
       let mut tasks = jmp_in(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -442,7 +486,7 @@ mod jmp_in {
       cpu.cycle = 0;
 
       let mut tasks = jmp_in(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -2,24 +2,27 @@ use crate::{
   cpu::{
     AddressingMode, CPU, Registers, Tasks,
     addressing::{
-      OffsetVariant,
+      AddressingModeTasks, OffsetVariant,
       absolute::{AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks, AccessVariant},
       indirect::{IndexIndirectXAddressingTasks, IndirectIndexYAddressingTasks},
       zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks},
     },
-    tasks::read_memory::ReadMemoryTasks,
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter},
+    tasks::read_memory::{AddressingReadMemoryTasks, ReadMemoryTasks},
   },
   memory::Memory,
 };
 
-struct LoadTasks {
+#[derive(Clone)]
+pub struct LoadTasks {
   done: bool,
-  read_memory_tasks: Box<dyn ReadMemoryTasks>,
+  read_memory_tasks: AddressingReadMemoryTasks,
   register: Registers,
 }
 
 impl LoadTasks {
-  pub fn new(read_memory_tasks: Box<dyn ReadMemoryTasks>, register: Registers) -> Self {
+  pub fn new(read_memory_tasks: AddressingReadMemoryTasks, register: Registers) -> Self {
     LoadTasks {
       done: false,
       read_memory_tasks,
@@ -51,93 +54,105 @@ impl Tasks for LoadTasks {
 
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    self.read_memory_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.read_memory_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
-fn ld(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) -> Box<dyn Tasks> {
+fn ld(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) -> InstructionTasks {
   let read_memory_tasks = cpu.read_memory(addr_mode);
-  Box::new(LoadTasks::new(read_memory_tasks, register))
+  LoadTasks::new(read_memory_tasks, register).into()
 }
 
-pub fn lda_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_im(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Immediate, Registers::Accumulator)
 }
 
-pub fn lda_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_zp(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPage, Registers::Accumulator)
 }
 
-pub fn lda_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_zpx(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPageX, Registers::Accumulator)
 }
 
-pub fn lda_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_a(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Absolute, Registers::Accumulator)
 }
 
-pub fn lda_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_ax(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::AbsoluteX, Registers::Accumulator)
 }
 
-pub fn lda_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_ay(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::AbsoluteY, Registers::Accumulator)
 }
 
-pub fn lda_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_inx(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::IndexIndirectX, Registers::Accumulator)
 }
 
-pub fn lda_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lda_iny(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::IndirectIndexY, Registers::Accumulator)
 }
 
-pub fn ldy_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldy_im(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Immediate, Registers::IndexY)
 }
 
-pub fn ldy_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldy_zp(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPage, Registers::IndexY)
 }
 
-pub fn ldy_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldy_zpx(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPageX, Registers::IndexY)
 }
 
-pub fn ldy_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldy_a(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Absolute, Registers::IndexY)
 }
 
-pub fn ldy_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldy_ax(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::AbsoluteX, Registers::IndexY)
 }
 
-pub fn ldx_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldx_im(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Immediate, Registers::IndexX)
 }
 
-pub fn ldx_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldx_zp(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPage, Registers::IndexX)
 }
 
-pub fn ldx_zpy(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldx_zpy(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::ZeroPageY, Registers::IndexX)
 }
 
-pub fn ldx_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldx_a(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::Absolute, Registers::IndexX)
 }
 
-pub fn ldx_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ldx_ay(cpu: &mut CPU) -> InstructionTasks {
   ld(cpu, AddressingMode::AbsoluteY, Registers::IndexX)
 }
 
-struct StoreTasks {
+#[derive(Clone)]
+pub struct StoreTasks {
   done: bool,
-  addressing_tasks: Box<dyn Tasks>,
+  addressing_tasks: AddressingModeTasks,
   src_register: Registers,
 }
 
 impl StoreTasks {
-  pub fn new(addressing_tasks: Box<dyn Tasks>, src_register: Registers) -> Self {
+  pub fn new(addressing_tasks: AddressingModeTasks, src_register: Registers) -> Self {
     StoreTasks {
       done: false,
       addressing_tasks,
@@ -170,103 +185,105 @@ impl Tasks for StoreTasks {
 
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    self.addressing_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.addressing_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
-pub fn sta_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
+pub fn sta_zp(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    ZeroPageAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_x()),
+pub fn sta_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    ZeroPageOffsetAddressingTasks::new_offset_by_x().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
+pub fn sta_a(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    AbsoluteAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(AbsoluteOffsetAddressingTasks::new(
-      OffsetVariant::X,
-      AccessVariant::Write,
-    )),
+pub fn sta_ax(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Write).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_ay(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(AbsoluteOffsetAddressingTasks::new(
-      OffsetVariant::Y,
-      AccessVariant::Write,
-    )),
+pub fn sta_ay(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::Y, AccessVariant::Write).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_inx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(IndexIndirectXAddressingTasks::new()),
+pub fn sta_inx(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    IndexIndirectXAddressingTasks::new().into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn sta_iny(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(IndirectIndexYAddressingTasks::new(AccessVariant::Write)),
+pub fn sta_iny(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    IndirectIndexYAddressingTasks::new(AccessVariant::Write).into(),
     Registers::Accumulator,
-  ))
+  )
+  .into()
 }
 
-pub fn stx_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
-    Registers::IndexX,
-  ))
+pub fn stx_zp(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(ZeroPageAddressingTasks::new().into(), Registers::IndexX).into()
 }
 
-pub fn stx_zpy(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_y()),
+pub fn stx_zpy(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    ZeroPageOffsetAddressingTasks::new_offset_by_y().into(),
     Registers::IndexX,
-  ))
+  )
+  .into()
 }
 
-pub fn stx_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
-    Registers::IndexX,
-  ))
+pub fn stx_a(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(AbsoluteAddressingTasks::new().into(), Registers::IndexX).into()
 }
 
-pub fn sty_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageAddressingTasks::new()),
-    Registers::IndexY,
-  ))
+pub fn sty_zp(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(ZeroPageAddressingTasks::new().into(), Registers::IndexY).into()
 }
 
-pub fn sty_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(ZeroPageOffsetAddressingTasks::new_offset_by_x()),
+pub fn sty_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(
+    ZeroPageOffsetAddressingTasks::new_offset_by_x().into(),
     Registers::IndexY,
-  ))
+  )
+  .into()
 }
 
-pub fn sty_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(StoreTasks::new(
-    Box::new(AbsoluteAddressingTasks::new()),
-    Registers::IndexY,
-  ))
+pub fn sty_a(_cpu: &mut CPU) -> InstructionTasks {
+  StoreTasks::new(AbsoluteAddressingTasks::new().into(), Registers::IndexY).into()
 }
 
 #[cfg(test)]
@@ -288,7 +305,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x44);
     }
@@ -300,7 +317,7 @@ mod lda {
       cpu.program_counter = 0x04;
 
       let mut tasks = lda_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -313,7 +330,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -337,7 +354,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x45);
     }
@@ -349,7 +366,7 @@ mod lda {
       cpu.program_counter = 0x00;
 
       let mut tasks = lda_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -362,7 +379,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -387,7 +404,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x55);
     }
@@ -400,7 +417,7 @@ mod lda {
       cpu.program_counter = 0x00;
 
       let mut tasks = lda_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x88);
     }
@@ -413,7 +430,7 @@ mod lda {
       cpu.program_counter = 0x00;
 
       let mut tasks = lda_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -427,7 +444,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -451,7 +468,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x45);
     }
@@ -463,7 +480,7 @@ mod lda {
       cpu.program_counter = 0x00;
 
       let mut tasks = lda_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -476,7 +493,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -508,7 +525,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, VALUE);
     }
@@ -521,7 +538,7 @@ mod lda {
       cpu.index_register_x = 0x02;
 
       let mut tasks = lda_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -535,7 +552,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -556,7 +573,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -588,7 +605,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, VALUE);
     }
@@ -601,7 +618,7 @@ mod lda {
       cpu.index_register_y = 0x02;
 
       let mut tasks = lda_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -615,7 +632,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -636,7 +653,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -676,7 +693,7 @@ mod lda {
       assert_eq!(cpu.accumulator, 0x0);
 
       let mut tasks = lda_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, VALUE);
     }
@@ -696,7 +713,7 @@ mod lda {
       cpu.program_counter = 0x00;
 
       let mut tasks = lda_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -718,7 +735,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -738,7 +755,7 @@ mod lda {
       cpu.cycle = 0;
 
       let mut tasks = lda_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -764,7 +781,7 @@ mod ldx {
       assert_eq!(cpu.index_register_x, 0x0);
 
       let mut tasks = ldx_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, 0x44);
     }
@@ -776,7 +793,7 @@ mod ldx {
       cpu.program_counter = 0x04;
 
       let mut tasks = ldx_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -789,7 +806,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -813,7 +830,7 @@ mod ldx {
       assert_eq!(cpu.index_register_x, 0x0);
 
       let mut tasks = ldx_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, 0x45);
     }
@@ -825,7 +842,7 @@ mod ldx {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldx_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -838,7 +855,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -863,7 +880,7 @@ mod ldx {
       assert_eq!(cpu.index_register_x, 0x0);
 
       let mut tasks = ldx_zpy(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, 0x55);
     }
@@ -876,7 +893,7 @@ mod ldx {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldx_zpy(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, 0x88);
     }
@@ -889,7 +906,7 @@ mod ldx {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldx_zpy(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -903,7 +920,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_zpy(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -927,7 +944,7 @@ mod ldx {
       assert_eq!(cpu.index_register_x, 0x0);
 
       let mut tasks = ldx_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, 0x45);
     }
@@ -939,7 +956,7 @@ mod ldx {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldx_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -952,7 +969,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -985,7 +1002,7 @@ mod ldx {
       assert_eq!(cpu.index_register_x, 0x0);
 
       let mut tasks = ldx_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_x, VALUE);
     }
@@ -998,7 +1015,7 @@ mod ldx {
       cpu.index_register_y = 0x02;
 
       let mut tasks = ldx_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1012,7 +1029,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1033,7 +1050,7 @@ mod ldx {
       cpu.cycle = 0;
 
       let mut tasks = ldx_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1059,7 +1076,7 @@ mod ldy {
       assert_eq!(cpu.index_register_y, 0x0);
 
       let mut tasks = ldy_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, 0x44);
     }
@@ -1071,7 +1088,7 @@ mod ldy {
       cpu.program_counter = 0x04;
 
       let mut tasks = ldy_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1084,7 +1101,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1108,7 +1125,7 @@ mod ldy {
       assert_eq!(cpu.index_register_y, 0x0);
 
       let mut tasks = ldy_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, 0x45);
     }
@@ -1120,7 +1137,7 @@ mod ldy {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldy_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1133,7 +1150,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -1158,7 +1175,7 @@ mod ldy {
       assert_eq!(cpu.index_register_y, 0x0);
 
       let mut tasks = ldy_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, 0x55);
     }
@@ -1171,7 +1188,7 @@ mod ldy {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldy_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, 0x88);
     }
@@ -1184,7 +1201,7 @@ mod ldy {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldy_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1198,7 +1215,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1222,7 +1239,7 @@ mod ldy {
       assert_eq!(cpu.index_register_y, 0x0);
 
       let mut tasks = ldy_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, 0x45);
     }
@@ -1234,7 +1251,7 @@ mod ldy {
       cpu.program_counter = 0x00;
 
       let mut tasks = ldy_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1247,7 +1264,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1280,7 +1297,7 @@ mod ldy {
       assert_eq!(cpu.index_register_y, 0x0);
 
       let mut tasks = ldy_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.index_register_y, VALUE);
     }
@@ -1293,7 +1310,7 @@ mod ldy {
       cpu.index_register_x = 0x02;
 
       let mut tasks = ldy_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1307,7 +1324,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1328,7 +1345,7 @@ mod ldy {
       cpu.cycle = 0;
 
       let mut tasks = ldy_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1354,7 +1371,7 @@ mod sta_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = sta_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR.into()], 0x02);
   }
@@ -1368,7 +1385,7 @@ mod sta_zp {
     cpu.cycle = 0;
 
     let mut tasks = sta_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -1395,7 +1412,7 @@ mod sta_zpx {
     cpu.program_counter = 0x00;
 
     let mut tasks = sta_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR_SUM_X], 0x05);
   }
@@ -1410,7 +1427,7 @@ mod sta_zpx {
     cpu.cycle = 0;
 
     let mut tasks = sta_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -1437,7 +1454,7 @@ mod sta_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = sta_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR as Word], 0x0A);
   }
@@ -1451,7 +1468,7 @@ mod sta_a {
     cpu.cycle = 0;
 
     let mut tasks = sta_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -1480,7 +1497,7 @@ mod sta_ax {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = sta_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR_OFFSET_BY_X], 0x08);
   }
@@ -1495,7 +1512,7 @@ mod sta_ax {
     cpu.cycle = 0;
 
     let mut tasks = sta_ax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 4);
   }
@@ -1524,7 +1541,7 @@ mod sta_ay {
     cpu.index_register_y = OFFSET;
 
     let mut tasks = sta_ay(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR_OFFSET_BY_Y], 0x08);
   }
@@ -1539,7 +1556,7 @@ mod sta_ay {
     cpu.cycle = 0;
 
     let mut tasks = sta_ay(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 4);
   }
@@ -1578,7 +1595,7 @@ mod sta_inx {
     cpu.index_register_x = OFFSET;
 
     let mut tasks = sta_inx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[EFFECTIVE_ADDRESS], 0xA9);
   }
@@ -1601,7 +1618,7 @@ mod sta_inx {
     cpu.cycle = 0;
 
     let mut tasks = sta_inx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -1632,7 +1649,7 @@ mod sta_iny {
     cpu.program_counter = 0x00;
 
     let mut tasks = sta_iny(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[EFFECTIVE_ADDRESS], 0xDF);
   }
@@ -1647,7 +1664,7 @@ mod sta_iny {
     cpu.cycle = 0;
 
     let mut tasks = sta_iny(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -1672,7 +1689,7 @@ mod stx_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = stx_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x02);
   }
@@ -1686,7 +1703,7 @@ mod stx_zp {
     cpu.cycle = 0;
 
     let mut tasks = stx_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -1713,7 +1730,7 @@ mod stx_zpy {
     cpu.program_counter = 0x00;
 
     let mut tasks = stx_zpy(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR_SUM_Y], 0x05);
   }
@@ -1728,7 +1745,7 @@ mod stx_zpy {
     cpu.cycle = 0;
 
     let mut tasks = stx_zpy(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -1755,7 +1772,7 @@ mod stx_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = stx_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR], 0x0A);
   }
@@ -1769,7 +1786,7 @@ mod stx_a {
     cpu.cycle = 0;
 
     let mut tasks = stx_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -1794,7 +1811,7 @@ mod sty_zp {
     cpu.program_counter = 0x00;
 
     let mut tasks = sty_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x02);
   }
@@ -1808,7 +1825,7 @@ mod sty_zp {
     cpu.cycle = 0;
 
     let mut tasks = sty_zp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -1835,7 +1852,7 @@ mod sty_zpx {
     cpu.program_counter = 0x00;
 
     let mut tasks = sty_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ZERO_PAGE_ADDR_SUM_X], 0x05);
   }
@@ -1850,7 +1867,7 @@ mod sty_zpx {
     cpu.cycle = 0;
 
     let mut tasks = sty_zpx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -1877,7 +1894,7 @@ mod sty_a {
     cpu.program_counter = 0x00;
 
     let mut tasks = sty_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[ADDR], 0x0A);
   }
@@ -1891,7 +1908,7 @@ mod sty_a {
     cpu.cycle = 0;
 
     let mut tasks = sty_a(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -2,11 +2,14 @@ use crate::{
   cpu::{
     AddressingMode, CPU, Registers, Tasks,
     addressing::{absolute::AbsoluteAddressingTasks, zero_page::ZeroPageAddressingTasks},
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter},
     tasks::read_memory::{AddressingReadMemoryTasks, ReadMemoryTasks},
   },
   memory::Memory,
 };
 
+#[derive(Clone)]
 enum Variant {
   And,
   Eor,
@@ -14,14 +17,15 @@ enum Variant {
   Bit,
 }
 
-struct LogicalTasks {
+#[derive(Clone)]
+pub struct LogicalTasks {
   done: bool,
-  read_memory_tasks: Box<dyn ReadMemoryTasks>,
+  read_memory_tasks: AddressingReadMemoryTasks,
   variant: Variant,
 }
 
 impl LogicalTasks {
-  pub fn new_and(read_memory_tasks: Box<dyn ReadMemoryTasks>) -> Self {
+  pub fn new_and(read_memory_tasks: AddressingReadMemoryTasks) -> Self {
     LogicalTasks {
       done: false,
       read_memory_tasks,
@@ -29,7 +33,7 @@ impl LogicalTasks {
     }
   }
 
-  pub fn new_eor(read_memory_tasks: Box<dyn ReadMemoryTasks>) -> Self {
+  pub fn new_eor(read_memory_tasks: AddressingReadMemoryTasks) -> Self {
     LogicalTasks {
       done: false,
       read_memory_tasks,
@@ -37,7 +41,7 @@ impl LogicalTasks {
     }
   }
 
-  pub fn new_ora(read_memory_tasks: Box<dyn ReadMemoryTasks>) -> Self {
+  pub fn new_ora(read_memory_tasks: AddressingReadMemoryTasks) -> Self {
     LogicalTasks {
       done: false,
       read_memory_tasks,
@@ -45,7 +49,7 @@ impl LogicalTasks {
     }
   }
 
-  pub fn new_bit(read_memory_tasks: Box<dyn ReadMemoryTasks>) -> Self {
+  pub fn new_bit(read_memory_tasks: AddressingReadMemoryTasks) -> Self {
     LogicalTasks {
       done: false,
       read_memory_tasks,
@@ -97,133 +101,146 @@ impl Tasks for LogicalTasks {
 
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    self.read_memory_tasks.save(state);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.read_memory_tasks.restore(state)?;
+    Ok(())
+  }
 }
 
-pub fn and(cpu: &mut CPU, addr_mode: AddressingMode) -> Box<dyn Tasks> {
+pub fn and(cpu: &mut CPU, addr_mode: AddressingMode) -> InstructionTasks {
   let read_memory_tasks = cpu.read_memory(addr_mode);
-  Box::new(LogicalTasks::new_and(read_memory_tasks))
+  LogicalTasks::new_and(read_memory_tasks).into()
 }
 
-pub fn and_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_im(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::Immediate)
 }
 
-pub fn and_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_zp(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::ZeroPage)
 }
 
-pub fn and_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_zpx(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::ZeroPageX)
 }
 
-pub fn and_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_a(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::Absolute)
 }
 
-pub fn and_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_ax(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::AbsoluteX)
 }
 
-pub fn and_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_ay(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::AbsoluteY)
 }
 
-pub fn and_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_inx(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::IndexIndirectX)
 }
 
-pub fn and_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn and_iny(cpu: &mut CPU) -> InstructionTasks {
   and(cpu, AddressingMode::IndirectIndexY)
 }
 
-pub fn eor(cpu: &mut CPU, addr_mode: AddressingMode) -> Box<dyn Tasks> {
+pub fn eor(cpu: &mut CPU, addr_mode: AddressingMode) -> InstructionTasks {
   let read_memory_tasks = cpu.read_memory(addr_mode);
-  Box::new(LogicalTasks::new_eor(read_memory_tasks))
+  LogicalTasks::new_eor(read_memory_tasks).into()
 }
 
-pub fn eor_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_im(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::Immediate)
 }
 
-pub fn eor_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_zp(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::ZeroPage)
 }
 
-pub fn eor_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_zpx(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::ZeroPageX)
 }
 
-pub fn eor_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_a(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::Absolute)
 }
 
-pub fn eor_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_ax(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::AbsoluteX)
 }
 
-pub fn eor_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_ay(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::AbsoluteY)
 }
 
-pub fn eor_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_inx(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::IndexIndirectX)
 }
 
-pub fn eor_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn eor_iny(cpu: &mut CPU) -> InstructionTasks {
   eor(cpu, AddressingMode::IndirectIndexY)
 }
 
-pub fn ora(cpu: &mut CPU, addr_mode: AddressingMode) -> Box<dyn Tasks> {
+pub fn ora(cpu: &mut CPU, addr_mode: AddressingMode) -> InstructionTasks {
   let read_memory_tasks = cpu.read_memory(addr_mode);
-  Box::new(LogicalTasks::new_ora(read_memory_tasks))
+  LogicalTasks::new_ora(read_memory_tasks).into()
 }
 
-pub fn ora_im(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_im(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::Immediate)
 }
 
-pub fn ora_zp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_zp(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::ZeroPage)
 }
 
-pub fn ora_zpx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_zpx(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::ZeroPageX)
 }
 
-pub fn ora_a(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_a(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::Absolute)
 }
 
-pub fn ora_ax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_ax(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::AbsoluteX)
 }
 
-pub fn ora_ay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_ay(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::AbsoluteY)
 }
 
-pub fn ora_inx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_inx(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::IndexIndirectX)
 }
 
-pub fn ora_iny(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ora_iny(cpu: &mut CPU) -> InstructionTasks {
   ora(cpu, AddressingMode::IndirectIndexY)
 }
 
-pub fn bit_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(LogicalTasks::new_bit(Box::new(
-    AddressingReadMemoryTasks::new_with_access_in_separate_cycle(Box::new(
-      ZeroPageAddressingTasks::new(),
-    )),
-  )))
+pub fn bit_zp(_cpu: &mut CPU) -> InstructionTasks {
+  LogicalTasks::new_bit(
+    AddressingReadMemoryTasks::new_with_access_in_separate_cycle(
+      ZeroPageAddressingTasks::new().into(),
+    ),
+  )
+  .into()
 }
 
-pub fn bit_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(LogicalTasks::new_bit(Box::new(
-    AddressingReadMemoryTasks::new_with_access_in_separate_cycle(Box::new(
-      AbsoluteAddressingTasks::new(),
-    )),
-  )))
+pub fn bit_a(_cpu: &mut CPU) -> InstructionTasks {
+  LogicalTasks::new_bit(
+    AddressingReadMemoryTasks::new_with_access_in_separate_cycle(
+      AbsoluteAddressingTasks::new().into(),
+    ),
+  )
+  .into()
 }
 
 #[cfg(test)]
@@ -245,7 +262,7 @@ mod ora {
       cpu.accumulator = 0x16;
 
       let mut tasks = ora_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -258,7 +275,7 @@ mod ora {
       cpu.accumulator = 0x86;
 
       let mut tasks = ora_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -272,7 +289,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -298,7 +315,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -311,7 +328,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -325,7 +342,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -352,7 +369,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -366,7 +383,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -381,7 +398,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -408,7 +425,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -421,7 +438,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -435,7 +452,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -466,7 +483,7 @@ mod ora {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = ora_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -480,7 +497,7 @@ mod ora {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = ora_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -495,7 +512,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -517,7 +534,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -548,7 +565,7 @@ mod ora {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = ora_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -562,7 +579,7 @@ mod ora {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = ora_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -577,7 +594,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -599,7 +616,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -637,7 +654,7 @@ mod ora {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = ora_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -658,7 +675,7 @@ mod ora {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = ora_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -680,7 +697,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -721,7 +738,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x36);
     }
@@ -742,7 +759,7 @@ mod ora {
       cpu.program_counter = 0x00;
 
       let mut tasks = ora_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -765,7 +782,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -786,7 +803,7 @@ mod ora {
       cpu.cycle = 0;
 
       let mut tasks = ora_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -817,7 +834,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b00100010);
     }
@@ -831,7 +848,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100000);
     }
@@ -845,7 +862,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -858,7 +875,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -888,7 +905,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b00100010);
     }
@@ -902,7 +919,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b01100000);
     }
@@ -916,7 +933,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -929,7 +946,7 @@ mod bit {
       cpu.accumulator = 0xF0;
 
       let mut tasks = bit_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -960,7 +977,7 @@ mod and {
       cpu.accumulator = 0x07;
 
       let mut tasks = and_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -973,7 +990,7 @@ mod and {
       cpu.accumulator = 0x86;
 
       let mut tasks = and_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -987,7 +1004,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1013,7 +1030,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1026,7 +1043,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1040,7 +1057,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -1067,7 +1084,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1081,7 +1098,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1096,7 +1113,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1123,7 +1140,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1136,7 +1153,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1150,7 +1167,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1181,7 +1198,7 @@ mod and {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = and_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1195,7 +1212,7 @@ mod and {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = and_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1210,7 +1227,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1232,7 +1249,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1263,7 +1280,7 @@ mod and {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = and_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1277,7 +1294,7 @@ mod and {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = and_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1292,7 +1309,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1314,7 +1331,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1352,7 +1369,7 @@ mod and {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = and_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1373,7 +1390,7 @@ mod and {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = and_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1395,7 +1412,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1436,7 +1453,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x02);
     }
@@ -1457,7 +1474,7 @@ mod and {
       cpu.program_counter = 0x00;
 
       let mut tasks = and_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1480,7 +1497,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1501,7 +1518,7 @@ mod and {
       cpu.cycle = 0;
 
       let mut tasks = and_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1532,7 +1549,7 @@ mod eor {
       cpu.accumulator = 0x07;
 
       let mut tasks = eor_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1545,7 +1562,7 @@ mod eor {
       cpu.accumulator = 0x07;
 
       let mut tasks = eor_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1559,7 +1576,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_im(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1585,7 +1602,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1598,7 +1615,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1612,7 +1629,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 2);
     }
@@ -1639,7 +1656,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1653,7 +1670,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1668,7 +1685,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1695,7 +1712,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1708,7 +1725,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1722,7 +1739,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1753,7 +1770,7 @@ mod eor {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = eor_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1767,7 +1784,7 @@ mod eor {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = eor_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1782,7 +1799,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1804,7 +1821,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1835,7 +1852,7 @@ mod eor {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = eor_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1849,7 +1866,7 @@ mod eor {
       cpu.index_register_y = OFFSET;
 
       let mut tasks = eor_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1864,7 +1881,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 3);
     }
@@ -1886,7 +1903,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_ay(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1924,7 +1941,7 @@ mod eor {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = eor_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -1945,7 +1962,7 @@ mod eor {
       cpu.index_register_x = OFFSET;
 
       let mut tasks = eor_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -1967,7 +1984,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_inx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -2008,7 +2025,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x82);
     }
@@ -2029,7 +2046,7 @@ mod eor {
       cpu.program_counter = 0x00;
 
       let mut tasks = eor_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.processor_status, 0b10100000);
     }
@@ -2052,7 +2069,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -2073,7 +2090,7 @@ mod eor {
       cpu.cycle = 0;
 
       let mut tasks = eor_iny(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
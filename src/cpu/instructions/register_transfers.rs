@@ -1,35 +1,25 @@
-use crate::cpu::{CPU, Registers, Tasks, tasks::transfer_register::TransferRegistersTasks};
+use crate::cpu::{
+  CPU, Registers, instructions::InstructionTasks, tasks::transfer_register::TransferRegistersTasks,
+};
 
-pub fn tax(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn tax(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::Accumulator,
-    Registers::IndexX,
-  ))
+  TransferRegistersTasks::new(Registers::Accumulator, Registers::IndexX).into()
 }
 
-pub fn txa(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn txa(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::IndexX,
-    Registers::Accumulator,
-  ))
+  TransferRegistersTasks::new(Registers::IndexX, Registers::Accumulator).into()
 }
 
-pub fn tay(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn tay(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::Accumulator,
-    Registers::IndexY,
-  ))
+  TransferRegistersTasks::new(Registers::Accumulator, Registers::IndexY).into()
 }
 
-pub fn tya(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn tya(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::IndexY,
-    Registers::Accumulator,
-  ))
+  TransferRegistersTasks::new(Registers::IndexY, Registers::Accumulator).into()
 }
 
 #[cfg(test)]
@@ -48,7 +38,7 @@ mod tax {
     cpu.accumulator = 0xDE;
 
     let mut tasks = tax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_x, 0xDE);
   }
@@ -61,7 +51,7 @@ mod tax {
     cpu.cycle = 0;
 
     let mut tasks = tax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -74,7 +64,7 @@ mod tax {
     cpu.processor_status = 0x00_u8.into();
 
     let mut tasks = tax(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10000000);
   }
@@ -96,7 +86,7 @@ mod txa {
     cpu.index_register_x = 0xDE;
 
     let mut tasks = txa(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.accumulator, 0xDE);
   }
@@ -109,7 +99,7 @@ mod txa {
     cpu.cycle = 0;
 
     let mut tasks = txa(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -122,7 +112,7 @@ mod txa {
     cpu.processor_status = 0x00_u8.into();
 
     let mut tasks = txa(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10000000);
   }
@@ -144,7 +134,7 @@ mod tay {
     cpu.accumulator = 0xDE;
 
     let mut tasks = tay(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_y, 0xDE);
   }
@@ -157,7 +147,7 @@ mod tay {
     cpu.cycle = 0;
 
     let mut tasks = tay(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -170,7 +160,7 @@ mod tay {
     cpu.processor_status = 0x00_u8.into();
 
     let mut tasks = tay(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10000000);
   }
@@ -192,7 +182,7 @@ mod tya {
     cpu.index_register_y = 0xDE;
 
     let mut tasks = tya(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.accumulator, 0xDE);
   }
@@ -205,7 +195,7 @@ mod tya {
     cpu.cycle = 0;
 
     let mut tasks = tya(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -218,7 +208,7 @@ mod tya {
     cpu.processor_status = 0x00_u8.into();
 
     let mut tasks = tya(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10000000);
   }
@@ -1,129 +1,109 @@
 use crate::cpu::{
-  CPU, Registers, Tasks,
+  CPU, Registers,
   addressing::{
     OffsetVariant,
     absolute::{AbsoluteAddressingTasks, AbsoluteOffsetAddressingTasks, AccessVariant},
     zero_page::{ZeroPageAddressingTasks, ZeroPageOffsetAddressingTasks},
   },
+  instructions::InstructionTasks,
   tasks::{modify_memory::ModifyMemoryTasks, modify_register::ModifyRegisterTasks},
 };
 
-pub fn asl_acc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn asl_acc(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_acc();
-  Box::new(ModifyRegisterTasks::new_shift_left(Registers::Accumulator))
+  ModifyRegisterTasks::new_shift_left(Registers::Accumulator).into()
 }
 
-pub fn asl_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_left(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn asl_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_left(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn asl_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_left(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn asl_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_left(ZeroPageOffsetAddressingTasks::new_offset_by_x().into()).into()
 }
 
-pub fn asl_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_left(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn asl_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_left(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn asl_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_left(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn asl_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_left(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
-pub fn lsr_acc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn lsr_acc(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_acc();
-  Box::new(ModifyRegisterTasks::new_shift_right(Registers::Accumulator))
+  ModifyRegisterTasks::new_shift_right(Registers::Accumulator).into()
 }
 
-pub fn lsr_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_right(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn lsr_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_right(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn lsr_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_right(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn lsr_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_right(ZeroPageOffsetAddressingTasks::new_offset_by_x().into()).into()
 }
 
-pub fn lsr_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_right(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn lsr_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_right(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn lsr_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_shift_right(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn lsr_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_shift_right(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
-pub fn rol_acc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn rol_acc(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_acc();
-  Box::new(ModifyRegisterTasks::new_rotate_left(Registers::Accumulator))
+  ModifyRegisterTasks::new_rotate_left(Registers::Accumulator).into()
 }
 
-pub fn rol_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_left(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn rol_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_left(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn rol_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_left(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn rol_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_left(ZeroPageOffsetAddressingTasks::new_offset_by_x().into()).into()
 }
 
-pub fn rol_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_left(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn rol_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_left(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn rol_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_left(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn rol_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_left(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
-pub fn ror_acc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn ror_acc(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_acc();
-  Box::new(ModifyRegisterTasks::new_rotate_right(
-    Registers::Accumulator,
-  ))
+  ModifyRegisterTasks::new_rotate_right(Registers::Accumulator).into()
 }
 
-pub fn ror_zp(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_right(Box::new(
-    ZeroPageAddressingTasks::new(),
-  )))
+pub fn ror_zp(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_right(ZeroPageAddressingTasks::new().into()).into()
 }
 
-pub fn ror_zpx(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_right(Box::new(
-    ZeroPageOffsetAddressingTasks::new_offset_by_x(),
-  )))
+pub fn ror_zpx(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_right(ZeroPageOffsetAddressingTasks::new_offset_by_x().into())
+    .into()
 }
 
-pub fn ror_a(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_right(Box::new(
-    AbsoluteAddressingTasks::new(),
-  )))
+pub fn ror_a(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_right(AbsoluteAddressingTasks::new().into()).into()
 }
 
-pub fn ror_ax(_cpu: &mut CPU) -> Box<dyn Tasks> {
-  Box::new(ModifyMemoryTasks::new_rotate_right(Box::new(
-    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify),
-  )))
+pub fn ror_ax(_cpu: &mut CPU) -> InstructionTasks {
+  ModifyMemoryTasks::new_rotate_right(
+    AbsoluteOffsetAddressingTasks::new(OffsetVariant::X, AccessVariant::Modify).into(),
+  )
+  .into()
 }
 
 #[cfg(test)]
@@ -147,7 +127,7 @@ mod asl {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = asl_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -161,7 +141,7 @@ mod asl {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = asl_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -175,7 +155,7 @@ mod asl {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = asl_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -189,7 +169,7 @@ mod asl {
         assert!(!cpu.processor_status.get_negative_flag());
 
         let mut tasks = asl_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_negative_flag());
       }
@@ -218,7 +198,7 @@ mod asl {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = asl_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -233,7 +213,7 @@ mod asl {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = asl_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -248,7 +228,7 @@ mod asl {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = asl_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -263,7 +243,7 @@ mod asl {
         assert!(!cpu.processor_status.get_negative_flag());
 
         let mut tasks = asl_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_negative_flag());
       }
@@ -291,7 +271,7 @@ mod asl {
       cpu.program_counter = 0x00;
 
       let mut tasks = asl_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x04);
     }
@@ -305,7 +285,7 @@ mod asl {
       cpu.cycle = 0;
 
       let mut tasks = asl_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -333,7 +313,7 @@ mod asl {
       cpu.program_counter = 0x00;
 
       let mut tasks = asl_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x04);
     }
@@ -346,7 +326,7 @@ mod asl {
       cpu.cycle = 0;
 
       let mut tasks = asl_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -376,7 +356,7 @@ mod asl {
       cpu.program_counter = 0x00;
 
       let mut tasks = asl_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0x04);
     }
@@ -390,7 +370,7 @@ mod asl {
       cpu.cycle = 0;
 
       let mut tasks = asl_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -419,7 +399,7 @@ mod asl {
       cpu.program_counter = 0x00;
 
       let mut tasks = asl_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0x04);
     }
@@ -432,7 +412,7 @@ mod asl {
       cpu.cycle = 0;
 
       let mut tasks = asl_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -463,7 +443,7 @@ mod asl {
       cpu.program_counter = 0x00;
 
       let mut tasks = asl_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0x04);
     }
@@ -477,7 +457,7 @@ mod asl {
       cpu.cycle = 0;
 
       let mut tasks = asl_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 6);
     }
@@ -505,7 +485,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = lsr_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -519,7 +499,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = lsr_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -533,7 +513,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = lsr_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -562,7 +542,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = lsr_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -577,7 +557,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = lsr_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -592,7 +572,7 @@ mod lsr {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = lsr_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -620,7 +600,7 @@ mod lsr {
       cpu.program_counter = 0x00;
 
       let mut tasks = lsr_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0x01);
     }
@@ -634,7 +614,7 @@ mod lsr {
       cpu.cycle = 0;
 
       let mut tasks = lsr_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -662,7 +642,7 @@ mod lsr {
       cpu.program_counter = 0x00;
 
       let mut tasks = lsr_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0x01);
     }
@@ -675,7 +655,7 @@ mod lsr {
       cpu.cycle = 0;
 
       let mut tasks = lsr_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -705,7 +685,7 @@ mod lsr {
       cpu.program_counter = 0x00;
 
       let mut tasks = lsr_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0x01);
     }
@@ -719,7 +699,7 @@ mod lsr {
       cpu.cycle = 0;
 
       let mut tasks = lsr_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -748,7 +728,7 @@ mod lsr {
       cpu.program_counter = 0x00;
 
       let mut tasks = lsr_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0x01);
     }
@@ -761,7 +741,7 @@ mod lsr {
       cpu.cycle = 0;
 
       let mut tasks = lsr_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -792,7 +772,7 @@ mod lsr {
       cpu.program_counter = 0x00;
 
       let mut tasks = lsr_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0x01);
     }
@@ -806,7 +786,7 @@ mod lsr {
       cpu.cycle = 0;
 
       let mut tasks = lsr_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 6);
     }
@@ -834,7 +814,7 @@ mod rol {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = rol_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -848,7 +828,7 @@ mod rol {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = rol_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -862,7 +842,7 @@ mod rol {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = rol_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -891,7 +871,7 @@ mod rol {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = rol_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -906,7 +886,7 @@ mod rol {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = rol_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -921,7 +901,7 @@ mod rol {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = rol_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -949,7 +929,7 @@ mod rol {
       cpu.accumulator = VALUE;
 
       let mut tasks = rol_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b00000100);
     }
@@ -962,7 +942,7 @@ mod rol {
       cpu.accumulator = VALUE;
 
       let mut tasks = rol_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b00000101);
     }
@@ -975,7 +955,7 @@ mod rol {
       cpu.accumulator = VALUE;
 
       let mut tasks = rol_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b00000100);
     }
@@ -989,7 +969,7 @@ mod rol {
       cpu.cycle = 0;
 
       let mut tasks = rol_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1017,7 +997,7 @@ mod rol {
       cpu.program_counter = 0x00;
 
       let mut tasks = rol_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b00000100);
     }
@@ -1030,7 +1010,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = rol_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b00000101);
     }
@@ -1043,7 +1023,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = rol_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b00000100);
     }
@@ -1056,7 +1036,7 @@ mod rol {
       cpu.cycle = 0;
 
       let mut tasks = rol_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1086,7 +1066,7 @@ mod rol {
       cpu.program_counter = 0x00;
 
       let mut tasks = rol_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b00000100);
     }
@@ -1100,7 +1080,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = rol_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b00000101);
     }
@@ -1114,7 +1094,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = rol_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b00000100);
     }
@@ -1128,7 +1108,7 @@ mod rol {
       cpu.cycle = 0;
 
       let mut tasks = rol_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1157,7 +1137,7 @@ mod rol {
       cpu.program_counter = 0x00;
 
       let mut tasks = rol_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b00000100);
     }
@@ -1170,7 +1150,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = rol_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b00000101);
     }
@@ -1183,7 +1163,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = rol_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b00000100);
     }
@@ -1196,7 +1176,7 @@ mod rol {
       cpu.cycle = 0;
 
       let mut tasks = rol_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1227,7 +1207,7 @@ mod rol {
       cpu.program_counter = 0x00;
 
       let mut tasks = rol_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b00000100);
     }
@@ -1241,7 +1221,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = rol_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b00000101);
     }
@@ -1255,7 +1235,7 @@ mod rol {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = rol_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b00000100);
     }
@@ -1269,7 +1249,7 @@ mod rol {
       cpu.cycle = 0;
 
       let mut tasks = rol_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 6);
     }
@@ -1297,7 +1277,7 @@ mod ror {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = ror_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -1311,7 +1291,7 @@ mod ror {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = ror_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -1325,7 +1305,7 @@ mod ror {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = ror_acc(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -1354,7 +1334,7 @@ mod ror {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = ror_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_carry_flag());
       }
@@ -1369,7 +1349,7 @@ mod ror {
         assert!(!cpu.processor_status.get_carry_flag());
 
         let mut tasks = ror_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_carry_flag());
       }
@@ -1384,7 +1364,7 @@ mod ror {
         assert!(!cpu.processor_status.get_zero_flag());
 
         let mut tasks = ror_zp(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(cpu.processor_status.get_zero_flag());
       }
@@ -1412,7 +1392,7 @@ mod ror {
       cpu.accumulator = VALUE;
 
       let mut tasks = ror_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b00000001);
     }
@@ -1425,7 +1405,7 @@ mod ror {
       cpu.accumulator = VALUE;
 
       let mut tasks = ror_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b10000001);
     }
@@ -1438,7 +1418,7 @@ mod ror {
       cpu.accumulator = VALUE;
 
       let mut tasks = ror_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.accumulator, 0b00000001);
     }
@@ -1452,7 +1432,7 @@ mod ror {
       cpu.cycle = 0;
 
       let mut tasks = ror_acc(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 1);
     }
@@ -1480,7 +1460,7 @@ mod ror {
       cpu.program_counter = 0x00;
 
       let mut tasks = ror_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b00000001);
     }
@@ -1493,7 +1473,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = ror_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b10000001);
     }
@@ -1506,7 +1486,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = ror_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ZERO_PAGE_ADDR as Word], 0b00000001);
     }
@@ -1519,7 +1499,7 @@ mod ror {
       cpu.cycle = 0;
 
       let mut tasks = ror_zp(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 4);
     }
@@ -1549,7 +1529,7 @@ mod ror {
       cpu.program_counter = 0x00;
 
       let mut tasks = ror_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b00000001);
     }
@@ -1563,7 +1543,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = ror_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b10000001);
     }
@@ -1577,7 +1557,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = ror_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ZERO_PAGE_ADDR + OFFSET) as Word], 0b00000001);
     }
@@ -1591,7 +1571,7 @@ mod ror {
       cpu.cycle = 0;
 
       let mut tasks = ror_zpx(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1620,7 +1600,7 @@ mod ror {
       cpu.program_counter = 0x00;
 
       let mut tasks = ror_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b00000001);
     }
@@ -1633,7 +1613,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = ror_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b10000001);
     }
@@ -1646,7 +1626,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = ror_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[ABSOLUTE_ADDR_LO as Word], 0b00000001);
     }
@@ -1659,7 +1639,7 @@ mod ror {
       cpu.cycle = 0;
 
       let mut tasks = ror_a(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 5);
     }
@@ -1690,7 +1670,7 @@ mod ror {
       cpu.program_counter = 0x00;
 
       let mut tasks = ror_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b00000001);
     }
@@ -1704,7 +1684,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(true);
 
       let mut tasks = ror_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b10000001);
     }
@@ -1718,7 +1698,7 @@ mod ror {
       cpu.processor_status.change_carry_flag(false);
 
       let mut tasks = ror_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[(ABSOLUTE_ADDR_LO + OFFSET) as Word], 0b00000001);
     }
@@ -1732,7 +1712,7 @@ mod ror {
       cpu.cycle = 0;
 
       let mut tasks = ror_ax(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 6);
     }
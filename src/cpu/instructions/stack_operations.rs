@@ -1,16 +1,27 @@
+use strum::FromRepr;
+
 use crate::{
-  cpu::{CPU, Registers, Tasks, tasks::transfer_register::TransferRegistersTasks},
+  cpu::{
+    CPU, Registers, Tasks,
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+    tasks::transfer_register::TransferRegistersTasks,
+  },
   memory::Memory,
 };
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum PushRegisterSteps {
   DummyFetch,
   PushToStack,
   Done,
 }
 
-struct PushRegisterTasks {
+enum_state_field!(PushRegisterSteps);
+
+#[derive(Clone)]
+pub struct PushRegisterTasks {
   register: Registers,
   step: PushRegisterSteps,
 }
@@ -54,23 +65,33 @@ impl Tasks for PushRegisterTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
-fn push_register(_cpu: &mut CPU, register: Registers) -> Box<dyn Tasks> {
-  Box::new(PushRegisterTasks::new(register))
+fn push_register(_cpu: &mut CPU, register: Registers) -> InstructionTasks {
+  PushRegisterTasks::new(register).into()
 }
 
-pub fn pha(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn pha(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   push_register(cpu, Registers::Accumulator)
 }
 
-pub fn php(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn php(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   push_register(cpu, Registers::ProcessorStatus)
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum PullRegisterSteps {
   DummyFetch,
   PreDecrementStackPointer,
@@ -78,7 +99,10 @@ enum PullRegisterSteps {
   Done,
 }
 
-struct PullRegisterTasks {
+enum_state_field!(PullRegisterSteps);
+
+#[derive(Clone)]
+pub struct PullRegisterTasks {
   register: Registers,
   step: PullRegisterSteps,
 }
@@ -106,7 +130,7 @@ impl Tasks for PullRegisterTasks {
         false
       }
       PullRegisterSteps::PreDecrementStackPointer => {
-        _ = memory[cpu.get_stack_ptr_address()]; // dummy read
+        memory.dummy_read(cpu.get_stack_ptr_address()); // dummy read
         cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
         self.step = PullRegisterSteps::PullFromStack;
         false
@@ -128,36 +152,39 @@ impl Tasks for PullRegisterTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
-fn pull_register(_cpu: &mut CPU, register: Registers) -> Box<dyn Tasks> {
-  Box::new(PullRegisterTasks::new(register))
+fn pull_register(_cpu: &mut CPU, register: Registers) -> InstructionTasks {
+  PullRegisterTasks::new(register).into()
 }
 
-pub fn pla(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn pla(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   pull_register(cpu, Registers::Accumulator)
 }
 
-pub fn plp(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn plp(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   pull_register(cpu, Registers::ProcessorStatus)
 }
 
-pub fn tsx(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn tsx(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::StackPointer,
-    Registers::IndexX,
-  ))
+  TransferRegistersTasks::new(Registers::StackPointer, Registers::IndexX).into()
 }
 
-pub fn txs(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn txs(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(TransferRegistersTasks::new(
-    Registers::IndexX,
-    Registers::StackPointer,
-  ))
+  TransferRegistersTasks::new(Registers::IndexX, Registers::StackPointer).into()
 }
 
 #[cfg(test)]
@@ -177,7 +204,7 @@ mod pha {
     cpu.accumulator = 0xDE;
 
     let mut tasks = pha(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[0x01FF], 0xDE);
   }
@@ -191,7 +218,7 @@ mod pha {
     cpu.cycle = 0;
 
     let mut tasks = pha(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -215,7 +242,7 @@ mod pla {
     cpu.accumulator = 0x00;
 
     let mut tasks = pla(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.accumulator, 0xDE);
   }
@@ -229,7 +256,7 @@ mod pla {
     cpu.cycle = 0;
 
     let mut tasks = pla(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -243,7 +270,7 @@ mod pla {
     cpu.processor_status.set(0x00);
 
     let mut tasks = pla(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10100000);
   }
@@ -266,7 +293,7 @@ mod php {
     cpu.stack_pointer = 0xFF;
 
     let mut tasks = php(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(memory[0x01FF], 0b10111010);
   }
@@ -280,7 +307,7 @@ mod php {
     cpu.cycle = 0;
 
     let mut tasks = php(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 2);
   }
@@ -304,7 +331,7 @@ mod plp {
     cpu.processor_status.set(0x00);
 
     let mut tasks = plp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10101010);
   }
@@ -319,7 +346,7 @@ mod plp {
     cpu.cycle = 0;
 
     let mut tasks = plp(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 3);
   }
@@ -341,7 +368,7 @@ mod txs {
     cpu.index_register_x = 0xDE;
 
     let mut tasks = txs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.stack_pointer, 0xDE);
   }
@@ -354,7 +381,7 @@ mod txs {
     cpu.cycle = 0;
 
     let mut tasks = txs(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -376,7 +403,7 @@ mod tsx {
     cpu.stack_pointer = 0xDE;
 
     let mut tasks = tsx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.index_register_x, 0xDE);
   }
@@ -389,7 +416,7 @@ mod tsx {
     cpu.cycle = 0;
 
     let mut tasks = tsx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -402,7 +429,7 @@ mod tsx {
     cpu.processor_status.set(0x00);
 
     let mut tasks = tsx(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b10100000);
   }
@@ -1,9 +1,15 @@
 use crate::{
-  cpu::{CPU, Tasks, processor_status::Flags},
+  cpu::{
+    CPU, Tasks,
+    instructions::InstructionTasks,
+    processor_status::Flags,
+    save_state::{SaveStateError, StateReader, StateWriter},
+  },
   memory::Memory,
 };
 
-struct ChangeStatusFlagTasks {
+#[derive(Clone)]
+pub struct ChangeStatusFlagTasks {
   flag: Flags,
   value: bool,
   done: bool,
@@ -34,43 +40,52 @@ impl Tasks for ChangeStatusFlagTasks {
     self.done = true;
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    Ok(())
+  }
 }
 
-fn change_flag_value(_cpu: &mut CPU, flag: Flags, value: bool) -> Box<dyn Tasks> {
-  Box::new(ChangeStatusFlagTasks::new(flag, value))
+fn change_flag_value(_cpu: &mut CPU, flag: Flags, value: bool) -> InstructionTasks {
+  ChangeStatusFlagTasks::new(flag, value).into()
 }
 
-pub fn clc(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn clc(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::Carry, false)
 }
 
-pub fn cld(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn cld(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::DecimalMode, false)
 }
 
-pub fn cli(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn cli(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::InterruptDisable, false)
 }
 
-pub fn clv(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn clv(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::Overflow, false)
 }
 
-pub fn sec(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sec(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::Carry, true)
 }
 
-pub fn sed(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sed(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::DecimalMode, true)
 }
 
-pub fn sei(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn sei(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
   change_flag_value(cpu, Flags::InterruptDisable, true)
 }
@@ -91,7 +106,7 @@ mod clc {
     cpu.processor_status.change_carry_flag(true);
 
     let mut tasks = clc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(!cpu.processor_status.get_carry_flag());
   }
@@ -104,7 +119,7 @@ mod clc {
     cpu.cycle = 0;
 
     let mut tasks = clc(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -126,7 +141,7 @@ mod cld {
     cpu.processor_status.change_decimal_mode_flag(true);
 
     let mut tasks = cld(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(!cpu.processor_status.get_decimal_mode_flag());
   }
@@ -139,7 +154,7 @@ mod cld {
     cpu.cycle = 0;
 
     let mut tasks = cld(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -161,7 +176,7 @@ mod cli {
     cpu.processor_status.change_interrupt_disable_flag(true);
 
     let mut tasks = cli(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(!cpu.processor_status.get_interrupt_disable_flag());
   }
@@ -174,7 +189,7 @@ mod cli {
     cpu.cycle = 0;
 
     let mut tasks = cli(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -196,7 +211,7 @@ mod clv {
     cpu.processor_status.change_overflow_flag(true);
 
     let mut tasks = clv(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(!cpu.processor_status.get_overflow_flag());
   }
@@ -209,7 +224,7 @@ mod clv {
     cpu.cycle = 0;
 
     let mut tasks = clv(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -231,7 +246,7 @@ mod sec {
     cpu.processor_status.change_carry_flag(false);
 
     let mut tasks = sec(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(cpu.processor_status.get_carry_flag());
   }
@@ -244,7 +259,7 @@ mod sec {
     cpu.cycle = 0;
 
     let mut tasks = sec(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -266,7 +281,7 @@ mod sed {
     cpu.processor_status.change_decimal_mode_flag(false);
 
     let mut tasks = sed(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(cpu.processor_status.get_decimal_mode_flag());
   }
@@ -279,7 +294,7 @@ mod sed {
     cpu.cycle = 0;
 
     let mut tasks = sed(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -301,7 +316,7 @@ mod sei {
     cpu.processor_status.change_interrupt_disable_flag(false);
 
     let mut tasks = sei(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert!(cpu.processor_status.get_interrupt_disable_flag());
   }
@@ -314,7 +329,7 @@ mod sei {
     cpu.cycle = 0;
 
     let mut tasks = sei(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
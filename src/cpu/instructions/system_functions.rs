@@ -1,10 +1,17 @@
+use strum::FromRepr;
+
 use crate::{
-  consts::{BRK_INTERRUPT_VECTOR, Byte},
-  cpu::{CPU, ChipVariant, Tasks},
+  consts::Byte,
+  cpu::{
+    CPU, ChipVariant, Tasks,
+    instructions::InstructionTasks,
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+  },
   memory::Memory,
 };
 
-struct NopTasks {
+#[derive(Clone)]
+pub struct NopTasks {
   done: bool,
 }
 
@@ -28,14 +35,24 @@ impl Tasks for NopTasks {
     self.done = true;
     true
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn nop(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn nop(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(NopTasks::new())
+  NopTasks::new().into()
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum BrkSteps {
   InitialFetchAndDiscard,
   PushProgramCounterHi,
@@ -46,16 +63,46 @@ enum BrkSteps {
   Done,
 }
 
-struct BrkTasks {
+enum_state_field!(BrkSteps);
+
+/// What started the BRK sequence. Interrupts run it with a forced BRK opcode.
+#[derive(Debug, Clone, Copy, PartialEq, FromRepr)]
+#[repr(u8)]
+pub enum Interrupt {
+  Brk,
+  Irq,
+  Nmi,
+}
+
+enum_state_field!(Interrupt);
+
+impl Interrupt {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Interrupt::Brk => "BRK",
+      Interrupt::Irq => "IRQ",
+      Interrupt::Nmi => "NMI",
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct BrkTasks {
   step: BrkSteps,
+  interrupt: Interrupt,
 }
 
 impl BrkTasks {
-  fn new() -> Self {
+  pub fn new(interrupt: Interrupt) -> Self {
     BrkTasks {
       step: BrkSteps::InitialFetchAndDiscard,
+      interrupt,
     }
   }
+
+  pub fn interrupt(&self) -> Interrupt {
+    self.interrupt
+  }
 }
 
 impl Tasks for BrkTasks {
@@ -66,8 +113,11 @@ impl Tasks for BrkTasks {
   fn tick(&mut self, cpu: &mut CPU, memory: &mut dyn Memory) -> bool {
     match self.step {
       BrkSteps::InitialFetchAndDiscard => {
-        _ = memory[cpu.program_counter]; // fetch and discard
-        cpu.increment_program_counter();
+        memory.dummy_read(cpu.program_counter); // fetch and discard
+        // interrupts return to the instruction they preempted
+        if self.interrupt == Interrupt::Brk {
+          cpu.increment_program_counter();
+        }
         self.step = BrkSteps::PushProgramCounterHi;
         false
       }
@@ -85,13 +135,16 @@ impl Tasks for BrkTasks {
       }
       BrkSteps::PushProcessorStatus => {
         let status: Byte = cpu.processor_status.into();
-        memory[cpu.get_stack_ptr_address()] = status | 0x10;
+        memory[cpu.get_stack_ptr_address()] = match self.interrupt {
+          Interrupt::Brk => status | 0x10,
+          Interrupt::Irq | Interrupt::Nmi => status & 0xEF,
+        };
         cpu.stack_pointer = cpu.stack_pointer.wrapping_sub(1);
         self.step = BrkSteps::AccessBrkVectorLo;
         false
       }
       BrkSteps::AccessBrkVectorLo => {
-        let lo = memory[BRK_INTERRUPT_VECTOR];
+        let lo = memory[cpu.interrupt_vector(self.interrupt)];
         cpu.set_program_counter_lo(lo);
         self.step = BrkSteps::AccessBrkVectorHi;
         false
@@ -102,7 +155,7 @@ impl Tasks for BrkTasks {
           cpu.processor_status.change_decimal_mode_flag(false);
         }
 
-        let hi = memory[BRK_INTERRUPT_VECTOR + 1];
+        let hi = memory[cpu.interrupt_vector(self.interrupt).wrapping_add(1)];
         cpu.set_program_counter_hi(hi);
 
         self.step = BrkSteps::Done;
@@ -113,14 +166,26 @@ impl Tasks for BrkTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+    state.write(&self.interrupt);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    self.interrupt = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn brk(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn brk(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(BrkTasks::new())
+  BrkTasks::new(Interrupt::Brk).into()
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum RtiSteps {
   DummyFetch,
   StackPointerPreDecrement,
@@ -130,7 +195,10 @@ enum RtiSteps {
   Done,
 }
 
-struct RtiTasks {
+enum_state_field!(RtiSteps);
+
+#[derive(Clone)]
+pub struct RtiTasks {
   step: RtiSteps,
 }
 
@@ -156,7 +224,7 @@ impl Tasks for RtiTasks {
         false
       }
       RtiSteps::StackPointerPreDecrement => {
-        _ = memory[cpu.get_stack_ptr_address()]; // dummy fetch
+        memory.dummy_read(cpu.get_stack_ptr_address()); // dummy fetch
         cpu.stack_pointer = cpu.stack_pointer.wrapping_add(1);
         self.step = RtiSteps::PopProcessorStatus;
         false
@@ -189,11 +257,20 @@ impl Tasks for RtiTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.step);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.step = state.read()?;
+    Ok(())
+  }
 }
 
-pub fn rti(cpu: &mut CPU) -> Box<dyn Tasks> {
+pub fn rti(cpu: &mut CPU) -> InstructionTasks {
   cpu.addr.reset_implicit();
-  Box::new(RtiTasks::new())
+  RtiTasks::new().into()
 }
 
 #[cfg(test)]
@@ -218,7 +295,7 @@ mod brk {
       cpu.program_counter = 0xABCD;
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[0x01FF], 0xAB);
       assert_eq!(memory[0x01FE], 0xCE);
@@ -237,7 +314,7 @@ mod brk {
       cpu.program_counter = 0x00;
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.program_counter, 0x9BAD);
     }
@@ -251,7 +328,7 @@ mod brk {
       cpu.processor_status.change_break_flag(false);
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[0x01FD], 0b00110000);
       assert!(!cpu.processor_status.get_break_flag());
@@ -266,7 +343,7 @@ mod brk {
       cpu.processor_status.change_break_flag(false);
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(memory[0x01FD], 0b00110000);
       assert!(cpu.processor_status.get_interrupt_disable_flag());
@@ -280,7 +357,7 @@ mod brk {
       cpu.cycle = 0;
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert_eq!(cpu.cycle, 6);
     }
@@ -304,7 +381,7 @@ mod brk {
         cpu.processor_status.change_decimal_mode_flag(true);
 
         let mut tasks = brk(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_decimal_mode_flag());
       }
@@ -324,7 +401,7 @@ mod brk {
         cpu.processor_status.change_decimal_mode_flag(true);
 
         let mut tasks = brk(&mut cpu);
-        run_tasks(&mut cpu, &mut *tasks, &mut memory);
+        run_tasks(&mut cpu, &mut tasks, &mut memory);
 
         assert!(!cpu.processor_status.get_decimal_mode_flag());
       }
@@ -347,7 +424,7 @@ mod brk {
       cpu.processor_status.change_decimal_mode_flag(true);
 
       let mut tasks = brk(&mut cpu);
-      run_tasks(&mut cpu, &mut *tasks, &mut memory);
+      run_tasks(&mut cpu, &mut tasks, &mut memory);
 
       assert!(cpu.processor_status.get_decimal_mode_flag());
     }
@@ -375,7 +452,7 @@ mod rti {
     cpu.program_counter = 0x00;
 
     let mut tasks = rti(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.processor_status, 0b11101111);
     assert_eq!(cpu.program_counter, 0xABCD);
@@ -389,7 +466,7 @@ mod rti {
     cpu.cycle = 0;
 
     let mut tasks = rti(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 5);
   }
@@ -411,7 +488,7 @@ mod nop {
     cpu.cycle = 0;
 
     let mut tasks = nop(&mut cpu);
-    run_tasks(&mut cpu, &mut *tasks, &mut memory);
+    run_tasks(&mut cpu, &mut tasks, &mut memory);
 
     assert_eq!(cpu.cycle, 1);
   }
@@ -0,0 +1,703 @@
+use crate::{
+  consts::{Byte, Word},
+  cpu::{
+    CPU, ChipVariant, Registers,
+    addressing::{AddressingMode, address::Address},
+    instructions::*,
+  },
+  memory::Memory,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+  Adc,
+  And,
+  Asl,
+  Bcc,
+  Bcs,
+  Beq,
+  Bit,
+  Bmi,
+  Bne,
+  Bpl,
+  Brk,
+  Bvc,
+  Bvs,
+  Clc,
+  Cld,
+  Cli,
+  Clv,
+  Cmp,
+  Cpx,
+  Cpy,
+  Dec,
+  Dex,
+  Dey,
+  Eor,
+  Inc,
+  Inx,
+  Iny,
+  Jmp,
+  Jsr,
+  Lda,
+  Ldx,
+  Ldy,
+  Lsr,
+  Nop,
+  Ora,
+  Pha,
+  Php,
+  Pla,
+  Plp,
+  Rol,
+  Ror,
+  Rti,
+  Rts,
+  Sbc,
+  Sec,
+  Sed,
+  Sei,
+  Sta,
+  Stx,
+  Sty,
+  Tax,
+  Tay,
+  Tsx,
+  Txa,
+  Txs,
+  Tya,
+}
+
+fn decode(opcode: Byte) -> Option<(Operation, AddressingMode)> {
+  use AddressingMode::*;
+  use Operation::*;
+
+  let decoded = match opcode {
+    ADC_IM => (Adc, Immediate),
+    ADC_ZP => (Adc, ZeroPage),
+    ADC_ZPX => (Adc, ZeroPageX),
+    ADC_A => (Adc, Absolute),
+    ADC_AX => (Adc, AbsoluteX),
+    ADC_AY => (Adc, AbsoluteY),
+    ADC_INX => (Adc, IndexIndirectX),
+    ADC_INY => (Adc, IndirectIndexY),
+    AND_IM => (And, Immediate),
+    AND_ZP => (And, ZeroPage),
+    AND_ZPX => (And, ZeroPageX),
+    AND_A => (And, Absolute),
+    AND_AX => (And, AbsoluteX),
+    AND_AY => (And, AbsoluteY),
+    AND_INX => (And, IndexIndirectX),
+    AND_INY => (And, IndirectIndexY),
+    ASL_ACC => (Asl, Accumulator),
+    ASL_ZP => (Asl, ZeroPage),
+    ASL_ZPX => (Asl, ZeroPageX),
+    ASL_A => (Asl, Absolute),
+    ASL_AX => (Asl, AbsoluteX),
+    BCC => (Bcc, Relative),
+    BCS => (Bcs, Relative),
+    BEQ => (Beq, Relative),
+    BIT_ZP => (Bit, ZeroPage),
+    BIT_A => (Bit, Absolute),
+    BMI => (Bmi, Relative),
+    BNE => (Bne, Relative),
+    BPL => (Bpl, Relative),
+    BRK => (Brk, Implicit),
+    BVC => (Bvc, Relative),
+    BVS => (Bvs, Relative),
+    CLC => (Clc, Implicit),
+    CLD => (Cld, Implicit),
+    CLI => (Cli, Implicit),
+    CLV => (Clv, Implicit),
+    CMP_IM => (Cmp, Immediate),
+    CMP_ZP => (Cmp, ZeroPage),
+    CMP_ZPX => (Cmp, ZeroPageX),
+    CMP_A => (Cmp, Absolute),
+    CMP_AX => (Cmp, AbsoluteX),
+    CMP_AY => (Cmp, AbsoluteY),
+    CMP_INX => (Cmp, IndexIndirectX),
+    CMP_INY => (Cmp, IndirectIndexY),
+    CPX_IM => (Cpx, Immediate),
+    CPX_ZP => (Cpx, ZeroPage),
+    CPX_A => (Cpx, Absolute),
+    CPY_IM => (Cpy, Immediate),
+    CPY_ZP => (Cpy, ZeroPage),
+    CPY_A => (Cpy, Absolute),
+    DEC_ZP => (Dec, ZeroPage),
+    DEC_ZPX => (Dec, ZeroPageX),
+    DEC_A => (Dec, Absolute),
+    DEC_AX => (Dec, AbsoluteX),
+    DEX_IM => (Dex, Implicit),
+    DEY_IM => (Dey, Implicit),
+    EOR_IM => (Eor, Immediate),
+    EOR_ZP => (Eor, ZeroPage),
+    EOR_ZPX => (Eor, ZeroPageX),
+    EOR_A => (Eor, Absolute),
+    EOR_AX => (Eor, AbsoluteX),
+    EOR_AY => (Eor, AbsoluteY),
+    EOR_INX => (Eor, IndexIndirectX),
+    EOR_INY => (Eor, IndirectIndexY),
+    INC_ZP => (Inc, ZeroPage),
+    INC_ZPX => (Inc, ZeroPageX),
+    INC_A => (Inc, Absolute),
+    INC_AX => (Inc, AbsoluteX),
+    INX_IM => (Inx, Implicit),
+    INY_IM => (Iny, Implicit),
+    JMP_A => (Jmp, Absolute),
+    JMP_IN => (Jmp, Indirect),
+    JSR_A => (Jsr, Absolute),
+    LDA_IM => (Lda, Immediate),
+    LDA_ZP => (Lda, ZeroPage),
+    LDA_ZPX => (Lda, ZeroPageX),
+    LDA_A => (Lda, Absolute),
+    LDA_AX => (Lda, AbsoluteX),
+    LDA_AY => (Lda, AbsoluteY),
+    LDA_INX => (Lda, IndexIndirectX),
+    LDA_INY => (Lda, IndirectIndexY),
+    LDX_IM => (Ldx, Immediate),
+    LDX_ZP => (Ldx, ZeroPage),
+    LDX_ZPY => (Ldx, ZeroPageY),
+    LDX_A => (Ldx, Absolute),
+    LDX_AY => (Ldx, AbsoluteY),
+    LDY_IM => (Ldy, Immediate),
+    LDY_ZP => (Ldy, ZeroPage),
+    LDY_ZPX => (Ldy, ZeroPageX),
+    LDY_A => (Ldy, Absolute),
+    LDY_AX => (Ldy, AbsoluteX),
+    LSR_ACC => (Lsr, Accumulator),
+    LSR_ZP => (Lsr, ZeroPage),
+    LSR_ZPX => (Lsr, ZeroPageX),
+    LSR_A => (Lsr, Absolute),
+    LSR_AX => (Lsr, AbsoluteX),
+    NOP => (Nop, Implicit),
+    ORA_IM => (Ora, Immediate),
+    ORA_ZP => (Ora, ZeroPage),
+    ORA_ZPX => (Ora, ZeroPageX),
+    ORA_A => (Ora, Absolute),
+    ORA_AX => (Ora, AbsoluteX),
+    ORA_AY => (Ora, AbsoluteY),
+    ORA_INX => (Ora, IndexIndirectX),
+    ORA_INY => (Ora, IndirectIndexY),
+    PHA => (Pha, Implicit),
+    PHP => (Php, Implicit),
+    PLA => (Pla, Implicit),
+    PLP => (Plp, Implicit),
+    ROL_ACC => (Rol, Accumulator),
+    ROL_ZP => (Rol, ZeroPage),
+    ROL_ZPX => (Rol, ZeroPageX),
+    ROL_A => (Rol, Absolute),
+    ROL_AX => (Rol, AbsoluteX),
+    ROR_ACC => (Ror, Accumulator),
+    ROR_ZP => (Ror, ZeroPage),
+    ROR_ZPX => (Ror, ZeroPageX),
+    ROR_A => (Ror, Absolute),
+    ROR_AX => (Ror, AbsoluteX),
+    RTI => (Rti, Implicit),
+    RTS => (Rts, Implicit),
+    SBC_IM => (Sbc, Immediate),
+    SBC_ZP => (Sbc, ZeroPage),
+    SBC_ZPX => (Sbc, ZeroPageX),
+    SBC_A => (Sbc, Absolute),
+    SBC_AX => (Sbc, AbsoluteX),
+    SBC_AY => (Sbc, AbsoluteY),
+    SBC_INX => (Sbc, IndexIndirectX),
+    SBC_INY => (Sbc, IndirectIndexY),
+    SEC => (Sec, Implicit),
+    SED => (Sed, Implicit),
+    SEI => (Sei, Implicit),
+    STA_ZP => (Sta, ZeroPage),
+    STA_ZPX => (Sta, ZeroPageX),
+    STA_A => (Sta, Absolute),
+    STA_AX => (Sta, AbsoluteX),
+    STA_AY => (Sta, AbsoluteY),
+    STA_INX => (Sta, IndexIndirectX),
+    STA_INY => (Sta, IndirectIndexY),
+    STX_ZP => (Stx, ZeroPage),
+    STX_ZPY => (Stx, ZeroPageY),
+    STX_A => (Stx, Absolute),
+    STY_ZP => (Sty, ZeroPage),
+    STY_ZPX => (Sty, ZeroPageX),
+    STY_A => (Sty, Absolute),
+    TAX => (Tax, Implicit),
+    TAY => (Tay, Implicit),
+    TSX => (Tsx, Implicit),
+    TXA => (Txa, Implicit),
+    TXS => (Txs, Implicit),
+    TYA => (Tya, Implicit),
+    _ => return None,
+  };
+
+  Some(decoded)
+}
+
+// Writes are ended immediately, so memories latching them see each one.
+fn write(memory: &mut dyn Memory, addr: Word, value: Byte) {
+  memory[addr] = value;
+  memory.end_cycle();
+}
+
+fn offset(base: Word, index: Byte) -> (Word, bool) {
+  let addr = base.wrapping_add(index as Word);
+  (addr, addr & 0xFF00 != base & 0xFF00)
+}
+
+impl CPU {
+  /// Executes the next instruction at once. Registers, memory and the cycle count end up as
+  /// after ticking through it, but dummy reads are skipped.
+  pub(super) fn execute_instruction(&mut self, memory: &mut dyn Memory) {
+    if let Some(interrupt) = self.take_interrupt() {
+      _ = memory.fetch_opcode(self.program_counter);
+      self.addr = Address::new();
+      self.sync = false;
+      self.cycle += 1 + self.interrupt(interrupt, memory);
+      memory.end_cycle();
+      return;
+    }
+
+    let (opcode, _) = self.fetch_opcode(memory);
+    let (operation, mode) =
+      decode(opcode).unwrap_or_else(|| panic!("illegal opcode found: {:#04X}", opcode));
+
+    self.addr = Address::new();
+    self.sync = false;
+    self.cycle += self.execute(operation, mode, memory);
+    memory.end_cycle();
+  }
+
+  // Returns the number of cycles after the opcode fetch.
+  fn execute(
+    &mut self,
+    operation: Operation,
+    mode: AddressingMode,
+    memory: &mut dyn Memory,
+  ) -> usize {
+    use Operation::*;
+
+    match operation {
+      Adc => self.with_operand(mode, memory, |cpu, value| cpu.add_with_carry(adc, value)),
+      Sbc => self.with_operand(mode, memory, |cpu, value| cpu.add_with_carry(sbc, value)),
+      And => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::Accumulator, cpu.accumulator & value)
+      }),
+      Eor => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::Accumulator, cpu.accumulator ^ value)
+      }),
+      Ora => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::Accumulator, cpu.accumulator | value)
+      }),
+      Bit => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_bit_status(cpu.accumulator, value)
+      }),
+      Cmp => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_cmp_status(Registers::Accumulator, value)
+      }),
+      Cpx => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_cmp_status(Registers::IndexX, value)
+      }),
+      Cpy => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_cmp_status(Registers::IndexY, value)
+      }),
+      Lda => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::Accumulator, value)
+      }),
+      Ldx => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::IndexX, value)
+      }),
+      Ldy => self.with_operand(mode, memory, |cpu, value| {
+        cpu.set_register(Registers::IndexY, value)
+      }),
+      Sta => self.store(mode, memory, self.accumulator),
+      Stx => self.store(mode, memory, self.index_register_x),
+      Sty => self.store(mode, memory, self.index_register_y),
+      Asl | Lsr | Rol | Ror | Inc | Dec if mode == AddressingMode::Accumulator => {
+        self.modify_register(operation, Registers::Accumulator)
+      }
+      Asl | Lsr | Rol | Ror | Inc | Dec => self.modify_memory(operation, mode, memory),
+      Inx => self.modify_register(Inc, Registers::IndexX),
+      Iny => self.modify_register(Inc, Registers::IndexY),
+      Dex => self.modify_register(Dec, Registers::IndexX),
+      Dey => self.modify_register(Dec, Registers::IndexY),
+      Bcc => self.branch(!self.processor_status.get_carry_flag(), memory),
+      Bcs => self.branch(self.processor_status.get_carry_flag(), memory),
+      Beq => self.branch(self.processor_status.get_zero_flag(), memory),
+      Bne => self.branch(!self.processor_status.get_zero_flag(), memory),
+      Bmi => self.branch(self.processor_status.get_negative_flag(), memory),
+      Bpl => self.branch(!self.processor_status.get_negative_flag(), memory),
+      Bvs => self.branch(self.processor_status.get_overflow_flag(), memory),
+      Bvc => self.branch(!self.processor_status.get_overflow_flag(), memory),
+      Jmp => self.jmp(mode, memory),
+      Jsr => {
+        let lo = self.fetch_operand(memory);
+        self.push(memory, self.get_program_counter_hi());
+        self.push(memory, self.get_program_counter_lo());
+        let hi = memory[self.program_counter];
+        self.program_counter = Word::from_le_bytes([lo, hi]);
+        5
+      }
+      Rts => {
+        let lo = self.pull(memory);
+        let hi = self.pull(memory);
+        self.program_counter = Word::from_le_bytes([lo, hi]).wrapping_add(1);
+        5
+      }
+      Rti => {
+        // break flag is always ignored when restoring from stack
+        self.processor_status = (self.pull(memory) & 0b11101111).into();
+        let lo = self.pull(memory);
+        let hi = self.pull(memory);
+        self.program_counter = Word::from_le_bytes([lo, hi]);
+        5
+      }
+      Brk => self.interrupt(Interrupt::Brk, memory),
+      Pha => {
+        self.push(memory, self.accumulator);
+        2
+      }
+      Php => {
+        let status: Byte = self.processor_status.into();
+        self.push(memory, status | 0b00010000);
+        2
+      }
+      Pla => {
+        let value = self.pull(memory);
+        self.set_register(Registers::Accumulator, value);
+        3
+      }
+      Plp => {
+        let value = self.pull(memory);
+        self.set_register(Registers::ProcessorStatus, value & 0b11101111);
+        3
+      }
+      Tax => self.transfer(Registers::Accumulator, Registers::IndexX),
+      Tay => self.transfer(Registers::Accumulator, Registers::IndexY),
+      Tsx => self.transfer(Registers::StackPointer, Registers::IndexX),
+      Txa => self.transfer(Registers::IndexX, Registers::Accumulator),
+      Txs => self.transfer(Registers::IndexX, Registers::StackPointer),
+      Tya => self.transfer(Registers::IndexY, Registers::Accumulator),
+      Clc => self.change_flag(|cpu| cpu.processor_status.change_carry_flag(false)),
+      Sec => self.change_flag(|cpu| cpu.processor_status.change_carry_flag(true)),
+      Cld => self.change_flag(|cpu| cpu.processor_status.change_decimal_mode_flag(false)),
+      Sed => self.change_flag(|cpu| cpu.processor_status.change_decimal_mode_flag(true)),
+      Cli => self.change_flag(|cpu| cpu.processor_status.change_interrupt_disable_flag(false)),
+      Sei => self.change_flag(|cpu| cpu.processor_status.change_interrupt_disable_flag(true)),
+      Clv => self.change_flag(|cpu| cpu.processor_status.change_overflow_flag(false)),
+      Nop => 1,
+    }
+  }
+
+  fn interrupt(&mut self, interrupt: Interrupt, memory: &mut dyn Memory) -> usize {
+    if interrupt == Interrupt::Brk {
+      self.increment_program_counter();
+    }
+    self.push(memory, self.get_program_counter_hi());
+    self.push(memory, self.get_program_counter_lo());
+    let status: Byte = self.processor_status.into();
+    let status = match interrupt {
+      Interrupt::Brk => status | 0b00010000,
+      Interrupt::Irq | Interrupt::Nmi => status & 0b11101111,
+    };
+    self.push(memory, status);
+    self.processor_status.change_interrupt_disable_flag(true);
+    if self.chip_variant != ChipVariant::NMOS {
+      self.processor_status.change_decimal_mode_flag(false);
+    }
+    let vector = self.interrupt_vector(interrupt);
+    let lo = memory[vector];
+    let hi = memory[vector.wrapping_add(1)];
+    self.program_counter = Word::from_le_bytes([lo, hi]);
+    6
+  }
+
+  fn fetch_operand(&mut self, memory: &dyn Memory) -> Byte {
+    let value = memory[self.program_counter];
+    self.increment_program_counter();
+    value
+  }
+
+  fn fetch_operand_word(&mut self, memory: &dyn Memory) -> Word {
+    let lo = self.fetch_operand(memory);
+    let hi = self.fetch_operand(memory);
+    Word::from_le_bytes([lo, hi])
+  }
+
+  fn push(&mut self, memory: &mut dyn Memory, value: Byte) {
+    write(memory, self.get_stack_ptr_address(), value);
+    self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+  }
+
+  fn pull(&mut self, memory: &dyn Memory) -> Byte {
+    self.stack_pointer = self.stack_pointer.wrapping_add(1);
+    memory[self.get_stack_ptr_address()]
+  }
+
+  // Returns the address and whether indexing crossed a page.
+  fn operand_address(&mut self, mode: AddressingMode, memory: &dyn Memory) -> (Word, bool) {
+    let zero_page_word =
+      |ptr: Byte| Word::from_le_bytes([memory[ptr as Word], memory[ptr.wrapping_add(1) as Word]]);
+
+    match mode {
+      AddressingMode::ZeroPage => (self.fetch_operand(memory) as Word, false),
+      AddressingMode::ZeroPageX => {
+        let addr = self
+          .fetch_operand(memory)
+          .wrapping_add(self.index_register_x);
+        (addr as Word, false)
+      }
+      AddressingMode::ZeroPageY => {
+        let addr = self
+          .fetch_operand(memory)
+          .wrapping_add(self.index_register_y);
+        (addr as Word, false)
+      }
+      AddressingMode::Absolute => (self.fetch_operand_word(memory), false),
+      AddressingMode::AbsoluteX => offset(self.fetch_operand_word(memory), self.index_register_x),
+      AddressingMode::AbsoluteY => offset(self.fetch_operand_word(memory), self.index_register_y),
+      AddressingMode::IndexIndirectX => {
+        let ptr = self
+          .fetch_operand(memory)
+          .wrapping_add(self.index_register_x);
+        (zero_page_word(ptr), false)
+      }
+      AddressingMode::IndirectIndexY => {
+        let ptr = self.fetch_operand(memory);
+        offset(zero_page_word(ptr), self.index_register_y)
+      }
+      _ => panic!("{mode} addressing has no operand address"),
+    }
+  }
+
+  fn with_operand(
+    &mut self,
+    mode: AddressingMode,
+    memory: &dyn Memory,
+    operation: impl FnOnce(&mut CPU, Byte),
+  ) -> usize {
+    let (value, cycles) = if mode == AddressingMode::Immediate {
+      (self.fetch_operand(memory), 1)
+    } else {
+      let (addr, page_crossed) = self.operand_address(mode, memory);
+      let cycles = match mode {
+        AddressingMode::ZeroPage => 2,
+        AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => 3 + page_crossed as usize,
+        AddressingMode::IndexIndirectX => 5,
+        AddressingMode::IndirectIndexY => 4 + page_crossed as usize,
+        _ => 3,
+      };
+      (memory[addr], cycles)
+    };
+
+    operation(self, value);
+    cycles
+  }
+
+  fn add_with_carry(&mut self, op: fn(Byte, Byte, bool) -> (Byte, bool, bool), value: Byte) {
+    let (result, carry, overflow) = op(
+      value,
+      self.accumulator,
+      self.processor_status.get_carry_flag(),
+    );
+    self.set_register(Registers::Accumulator, result);
+    self.processor_status.change_carry_flag(carry);
+    self.processor_status.change_overflow_flag(overflow);
+  }
+
+  fn store(&mut self, mode: AddressingMode, memory: &mut dyn Memory, value: Byte) -> usize {
+    let (addr, _) = self.operand_address(mode, memory);
+    write(memory, addr, value);
+
+    match mode {
+      AddressingMode::ZeroPage => 2,
+      AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => 4,
+      AddressingMode::IndexIndirectX | AddressingMode::IndirectIndexY => 5,
+      _ => 3,
+    }
+  }
+
+  fn modified(&self, operation: Operation, value: Byte) -> Byte {
+    let carry = self.processor_status.get_carry_flag() as Byte;
+    match operation {
+      Operation::Inc => value.wrapping_add(1),
+      Operation::Dec => value.wrapping_sub(1),
+      Operation::Asl => value << 1,
+      Operation::Lsr => value >> 1,
+      Operation::Rol => (value << 1) | carry,
+      Operation::Ror => (value >> 1) | (carry << 7),
+      _ => panic!("{operation:?} does not modify values"),
+    }
+  }
+
+  fn change_carry_by_shift(&mut self, operation: Operation, previous_value: Byte) {
+    match operation {
+      Operation::Asl | Operation::Rol => {
+        self
+          .processor_status
+          .change_carry_flag(previous_value & 0b10000000 > 0);
+      }
+      Operation::Lsr | Operation::Ror => {
+        self
+          .processor_status
+          .change_carry_flag(previous_value & 0b00000001 > 0);
+      }
+      _ => {}
+    }
+  }
+
+  fn modify_register(&mut self, operation: Operation, register: Registers) -> usize {
+    let previous_value = self.get_register(register);
+    self.set_register(register, self.modified(operation, previous_value));
+    self.change_carry_by_shift(operation, previous_value);
+    1
+  }
+
+  fn modify_memory(
+    &mut self,
+    operation: Operation,
+    mode: AddressingMode,
+    memory: &mut dyn Memory,
+  ) -> usize {
+    let (addr, _) = self.operand_address(mode, memory);
+    let previous_value = memory[addr];
+    // the unmodified value is written back first, as on hardware
+    write(memory, addr, previous_value);
+    let value = self.modified(operation, previous_value);
+    write(memory, addr, value);
+    self.set_status_of_value(value);
+    self.change_carry_by_shift(operation, previous_value);
+
+    match mode {
+      AddressingMode::ZeroPage => 4,
+      AddressingMode::AbsoluteX => 6,
+      _ => 5,
+    }
+  }
+
+  fn branch(&mut self, condition: bool, memory: &dyn Memory) -> usize {
+    let offset = self.fetch_operand(memory) as i8;
+    if !condition {
+      return 1;
+    }
+
+    let target = self.program_counter.wrapping_add_signed(offset as i16);
+    let page_crossed = target & 0xFF00 != self.program_counter & 0xFF00;
+    self.program_counter = target;
+    2 + page_crossed as usize
+  }
+
+  fn jmp(&mut self, mode: AddressingMode, memory: &dyn Memory) -> usize {
+    let addr = self.fetch_operand_word(memory);
+    if mode == AddressingMode::Absolute {
+      self.program_counter = addr;
+      return 2;
+    }
+
+    let lo = memory[addr];
+    if self.chip_variant == ChipVariant::NMOS {
+      // the NMOS chip does not carry into the high byte of the pointer
+      let [addr_lo, addr_hi] = addr.to_le_bytes();
+      let hi = memory[Word::from_le_bytes([addr_lo.wrapping_add(1), addr_hi])];
+      self.program_counter = Word::from_le_bytes([lo, hi]);
+      4
+    } else {
+      let hi = memory[addr.wrapping_add(1)];
+      self.program_counter = Word::from_le_bytes([lo, hi]);
+      5
+    }
+  }
+
+  fn transfer(&mut self, src: Registers, tgt: Registers) -> usize {
+    self.transfer_registers(src, tgt);
+    1
+  }
+
+  fn change_flag(&mut self, change: impl FnOnce(&mut CPU)) -> usize {
+    change(self);
+    1
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod execute_instruction {
+    use crate::{
+      consts::{Byte, Word},
+      cpu::{
+        ChipVariant, ExecutionMode, builder::CpuBuilder, instructions::INSTRUCTIONS,
+        interpreter::decode,
+      },
+      memory::Generic64kMem,
+      power_on::Rng,
+    };
+
+    const CASES_PER_OPCODE: usize = 32;
+
+    #[test]
+    fn should_decode_every_implemented_opcode() {
+      for opcode in 0..=Byte::MAX {
+        assert_eq!(
+          decode(opcode).is_some(),
+          INSTRUCTIONS[opcode as usize].is_some(),
+          "opcode {opcode:#04X}"
+        );
+      }
+    }
+
+    #[test]
+    fn should_match_cycle_stepped_execution() {
+      let mut rng = Rng::new(0x6502);
+      let base: Vec<Byte> = (0..=Word::MAX).map(|_| rng.next_byte()).collect();
+
+      for variant in [
+        ChipVariant::NMOS,
+        ChipVariant::RockwellCMOS,
+        ChipVariant::WDCCMOS,
+      ] {
+        for opcode in (0..=Byte::MAX).filter(|op| INSTRUCTIONS[*op as usize].is_some()) {
+          for case in 0..CASES_PER_OPCODE {
+            let pc = 0x0200 + (rng.next_u64() % 0xEE00) as Word;
+            let mut data = base.clone();
+            for byte in &mut data[..0x0200] {
+              *byte = rng.next_byte();
+            }
+            data[pc as usize] = opcode;
+            data[pc as usize + 1] = rng.next_byte();
+            data[pc as usize + 2] = rng.next_byte();
+            // JMP ($FFFF) wraps its pointer on every variant
+            if opcode == 0x6C && case == 0 {
+              data[pc as usize + 1] = 0xFF;
+              data[pc as usize + 2] = 0xFF;
+            }
+
+            let builder = CpuBuilder::new()
+              .variant(variant)
+              .reset(false)
+              .pc(pc)
+              .sp(rng.next_byte())
+              .a(rng.next_byte())
+              .x(rng.next_byte())
+              .y(rng.next_byte())
+              .p(rng.next_byte() & 0b1100_1111);
+            let [stepped, interpreted] =
+              [ExecutionMode::Cycle, ExecutionMode::Instruction].map(|mode| {
+                let mut memory = Generic64kMem::new();
+                memory.data = data.clone();
+                let mut cpu = builder.clone().execution_mode(mode).build(&memory);
+                let result = cpu.step(&mut memory);
+                (cpu.state(), result, memory.data)
+              });
+
+            assert_eq!(
+              (stepped.0, stepped.1),
+              (interpreted.0, interpreted.1),
+              "{variant:?} opcode {opcode:#04X} at {pc:#06X}"
+            );
+            assert!(
+              stepped.2 == interpreted.2,
+              "{variant:?} opcode {opcode:#04X} at {pc:#06X}: memory differs"
+            );
+          }
+        }
+      }
+    }
+  }
+}
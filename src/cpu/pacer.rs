@@ -0,0 +1,303 @@
+use std::time::{Duration, Instant};
+
+use crate::{cpu::CPU, memory::Memory};
+
+/// Source of wall-clock time for a `Pacer`.
+pub trait Clock {
+  /// Time elapsed since an arbitrary, fixed point.
+  fn now(&self) -> Duration;
+  fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock {
+  start: Instant,
+}
+
+impl Default for SystemClock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SystemClock {
+  pub fn new() -> Self {
+    SystemClock {
+      start: Instant::now(),
+    }
+  }
+}
+
+impl Clock for SystemClock {
+  fn now(&self) -> Duration {
+    self.start.elapsed()
+  }
+
+  fn sleep(&mut self, duration: Duration) {
+    std::thread::sleep(duration);
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PacingMode {
+  /// Slices are run at the target frequency.
+  #[default]
+  RealTime,
+  /// Slices are run as fast as possible.
+  Turbo,
+  /// Nothing is run, each slice only waits for its duration.
+  Paused,
+  /// A single cycle is run per slice, without waiting.
+  SingleCycle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PacingReport {
+  pub cycles: usize,
+  /// Emulated time minus wall-clock time at the end of the slice, in seconds. Negative when
+  /// the emulation can't keep up with the target frequency.
+  pub drift: f64,
+}
+
+/// Runs a CPU at a target clock frequency by running slices of cycles and sleeping until
+/// wall-clock time catches up with them. Time lost when the host can't keep up is made up by
+/// later slices, use `resync` to drop it instead.
+pub struct Pacer<C: Clock = SystemClock> {
+  clock: C,
+  frequency: f64,
+  slice_cycles: usize,
+  mode: PacingMode,
+  // wall-clock time at which `cycles` started being counted
+  epoch: Duration,
+  cycles: usize,
+}
+
+impl Pacer {
+  pub fn new(frequency: f64) -> Self {
+    Pacer::with_clock(frequency, SystemClock::new())
+  }
+}
+
+impl<C: Clock> Pacer<C> {
+  /// Creates a pacer running at `frequency` Hz in slices of 1 ms.
+  pub fn with_clock(frequency: f64, clock: C) -> Self {
+    assert!(frequency > 0.0, "frequency has to be positive");
+    let epoch = clock.now();
+    Pacer {
+      clock,
+      frequency,
+      slice_cycles: ((frequency / 1000.0).round() as usize).max(1),
+      mode: PacingMode::default(),
+      epoch,
+      cycles: 0,
+    }
+  }
+
+  pub fn slice_cycles(mut self, cycles: usize) -> Self {
+    self.slice_cycles = cycles.max(1);
+    self
+  }
+
+  pub fn frequency(&self) -> f64 {
+    self.frequency
+  }
+
+  pub fn mode(&self) -> PacingMode {
+    self.mode
+  }
+
+  pub fn set_mode(&mut self, mode: PacingMode) {
+    self.mode = mode;
+    self.resync();
+  }
+
+  pub fn clock(&self) -> &C {
+    &self.clock
+  }
+
+  pub fn clock_mut(&mut self) -> &mut C {
+    &mut self.clock
+  }
+
+  /// Forgets any drift, pacing continues from the current wall-clock time.
+  pub fn resync(&mut self) {
+    self.epoch = self.clock.now();
+    self.cycles = 0;
+  }
+
+  /// Runs one slice according to the mode, see `PacingMode`.
+  pub fn run_slice(&mut self, cpu: &mut CPU, memory: &mut dyn Memory) -> PacingReport {
+    let cycles = match self.mode {
+      PacingMode::Paused => {
+        self.clock.sleep(Duration::from_secs_f64(
+          self.slice_cycles as f64 / self.frequency,
+        ));
+        0
+      }
+      PacingMode::SingleCycle => {
+        cpu.tick(memory);
+        1
+      }
+      PacingMode::RealTime | PacingMode::Turbo => {
+        cpu.run_for_cycles(memory, self.slice_cycles).cycles
+      }
+    };
+    self.cycles += cycles;
+
+    if self.mode == PacingMode::RealTime {
+      let ahead = self.drift();
+      if ahead > 0.0 {
+        self.clock.sleep(Duration::from_secs_f64(ahead));
+      }
+    }
+
+    let drift = self.drift();
+    if self.mode != PacingMode::RealTime {
+      self.resync();
+    }
+
+    PacingReport { cycles, drift }
+  }
+
+  fn drift(&self) -> f64 {
+    let emulated = self.cycles as f64 / self.frequency;
+    let elapsed = (self.clock.now() - self.epoch).as_secs_f64();
+    emulated - elapsed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use crate::{
+    cpu::{
+      CPU,
+      pacer::{Clock, Pacer},
+    },
+    memory::Generic64kMem,
+  };
+
+  // Time only passes when slept or advanced explicitly.
+  #[derive(Default)]
+  struct FakeClock {
+    now: Duration,
+    slept: Vec<Duration>,
+  }
+
+  impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+      self.now
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+      self.now += duration;
+      self.slept.push(duration);
+    }
+  }
+
+  fn nops() -> (CPU, Generic64kMem) {
+    let mut memory = Generic64kMem::new();
+    memory.data.fill(0xEA);
+    let mut cpu = CPU::new_nmos();
+    let mut state = cpu.state();
+    state.pc = 0x0200;
+    cpu.set_state(state);
+    (cpu, memory)
+  }
+
+  #[cfg(test)]
+  mod run_slice {
+    use crate::cpu::pacer::{PacingMode, PacingReport};
+
+    use super::*;
+
+    #[test]
+    fn should_sleep_for_emulated_time_of_slice() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut = Pacer::with_clock(1_000_000.0, FakeClock::default());
+
+      let report = uut.run_slice(&mut cpu, &mut memory);
+      uut.run_slice(&mut cpu, &mut memory);
+
+      assert_eq!(
+        report,
+        PacingReport {
+          cycles: 1000,
+          drift: 0.0
+        }
+      );
+      assert_eq!(uut.clock().slept, [Duration::from_millis(1); 2]);
+      assert_eq!(cpu.state().cycle, 2000);
+    }
+
+    #[test]
+    fn should_report_negative_drift_when_host_is_too_slow() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut =
+        Pacer::with_clock(1_789_773.0, FakeClock::default()).slice_cycles(1_789_773 / 100);
+      uut.clock_mut().now += Duration::from_millis(15);
+
+      let report = uut.run_slice(&mut cpu, &mut memory);
+
+      assert!(uut.clock().slept.is_empty());
+      assert!((report.drift + 0.005).abs() < 1e-6, "{}", report.drift);
+    }
+
+    #[test]
+    fn should_make_up_for_lost_time_until_resynced() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut = Pacer::with_clock(1_000_000.0, FakeClock::default());
+      uut.clock_mut().now += Duration::from_micros(1500);
+
+      uut.run_slice(&mut cpu, &mut memory);
+      uut.run_slice(&mut cpu, &mut memory);
+      assert_eq!(uut.clock().slept, [Duration::from_micros(500)]);
+
+      uut.clock_mut().now += Duration::from_millis(5);
+      uut.resync();
+      uut.run_slice(&mut cpu, &mut memory);
+      assert_eq!(uut.clock().slept[1], Duration::from_millis(1));
+    }
+
+    #[test]
+    fn should_not_sleep_in_turbo_mode() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut = Pacer::with_clock(1_000_000.0, FakeClock::default()).slice_cycles(500);
+      uut.set_mode(PacingMode::Turbo);
+
+      let report = uut.run_slice(&mut cpu, &mut memory);
+
+      assert_eq!(report.cycles, 500);
+      assert!(uut.clock().slept.is_empty());
+    }
+
+    #[test]
+    fn should_not_run_when_paused_nor_catch_up_after() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut = Pacer::with_clock(1_000_000.0, FakeClock::default());
+      uut.set_mode(PacingMode::Paused);
+
+      assert_eq!(uut.run_slice(&mut cpu, &mut memory).cycles, 0);
+      assert_eq!(uut.clock().slept, [Duration::from_millis(1)]);
+      uut.clock_mut().now += Duration::from_secs(1);
+      uut.set_mode(PacingMode::RealTime);
+      uut.run_slice(&mut cpu, &mut memory);
+
+      assert_eq!(cpu.state().cycle, 1000);
+      assert_eq!(uut.clock().slept, [Duration::from_millis(1); 2]);
+    }
+
+    #[test]
+    fn should_run_single_cycle_without_sleeping() {
+      let (mut cpu, mut memory) = nops();
+      let mut uut = Pacer::with_clock(1_000_000.0, FakeClock::default());
+      uut.set_mode(PacingMode::SingleCycle);
+
+      let report = uut.run_slice(&mut cpu, &mut memory);
+
+      assert_eq!(report.cycles, 1);
+      assert_eq!(cpu.state().cycle, 1);
+      assert!(uut.clock().slept.is_empty());
+    }
+  }
+}
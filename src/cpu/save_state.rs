@@ -0,0 +1,348 @@
+use std::fmt::Display;
+
+use crate::{
+  consts::{Byte, Word},
+  cpu::{
+    CPU, ChipVariant, ExecutionMode, InstructionExecution,
+    instructions::{INSTRUCTIONS, InstructionTasks},
+    tasks::Tasks,
+  },
+};
+
+const MAGIC: [Byte; 4] = *b"C65S";
+pub const VERSION: Byte = 1;
+
+#[derive(Debug, PartialEq)]
+pub enum SaveStateError {
+  InvalidMagic,
+  UnsupportedVersion(Byte),
+  UnexpectedEnd,
+  InvalidValue(&'static str),
+  TrailingData,
+}
+
+impl Display for SaveStateError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SaveStateError::InvalidMagic => write!(f, "not a CPU save state"),
+      SaveStateError::UnsupportedVersion(version) => {
+        write!(f, "unsupported save state version {version}")
+      }
+      SaveStateError::UnexpectedEnd => write!(f, "unexpected end of save state"),
+      SaveStateError::InvalidValue(field) => write!(f, "invalid value of {field}"),
+      SaveStateError::TrailingData => write!(f, "unexpected data after end of save state"),
+    }
+  }
+}
+
+impl std::error::Error for SaveStateError {}
+
+/// Value that can be stored in a save state.
+pub trait StateField: Sized {
+  fn write(&self, state: &mut StateWriter);
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError>;
+}
+
+#[derive(Default)]
+pub struct StateWriter {
+  bytes: Vec<Byte>,
+}
+
+impl StateWriter {
+  pub fn write<T: StateField>(&mut self, value: &T) {
+    value.write(self);
+  }
+
+  fn push(&mut self, bytes: &[Byte]) {
+    self.bytes.extend_from_slice(bytes);
+  }
+}
+
+pub struct StateReader<'a> {
+  bytes: &'a [Byte],
+}
+
+impl StateReader<'_> {
+  pub fn read<T: StateField>(&mut self) -> Result<T, SaveStateError> {
+    T::read(self)
+  }
+
+  fn take<const N: usize>(&mut self) -> Result<[Byte; N], SaveStateError> {
+    let Some((head, tail)) = self.bytes.split_first_chunk::<N>() else {
+      return Err(SaveStateError::UnexpectedEnd);
+    };
+    self.bytes = tail;
+    Ok(*head)
+  }
+}
+
+impl StateField for Byte {
+  fn write(&self, state: &mut StateWriter) {
+    state.push(&[*self]);
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    Ok(state.take::<1>()?[0])
+  }
+}
+
+impl StateField for Word {
+  fn write(&self, state: &mut StateWriter) {
+    state.push(&self.to_le_bytes());
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    Ok(Word::from_le_bytes(state.take()?))
+  }
+}
+
+impl StateField for usize {
+  fn write(&self, state: &mut StateWriter) {
+    state.push(&(*self as u64).to_le_bytes());
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    usize::try_from(u64::from_le_bytes(state.take()?))
+      .map_err(|_| SaveStateError::InvalidValue("usize"))
+  }
+}
+
+impl StateField for bool {
+  fn write(&self, state: &mut StateWriter) {
+    state.push(&[*self as Byte]);
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    match state.take::<1>()? {
+      [0] => Ok(false),
+      [1] => Ok(true),
+      _ => Err(SaveStateError::InvalidValue("bool")),
+    }
+  }
+}
+
+impl<T: StateField> StateField for Option<T> {
+  fn write(&self, state: &mut StateWriter) {
+    state.write(&self.is_some());
+    if let Some(value) = self {
+      state.write(value);
+    }
+  }
+
+  fn read(state: &mut StateReader) -> Result<Self, SaveStateError> {
+    if state.read::<bool>()? {
+      Ok(Some(state.read()?))
+    } else {
+      Ok(None)
+    }
+  }
+}
+
+/// Implements `StateField` for fieldless `#[repr(u8)]` enums deriving `Copy` and `FromRepr`.
+macro_rules! enum_state_field {
+  ($($ty:ty),+) => {
+    $(
+      impl $crate::cpu::save_state::StateField for $ty {
+        fn write(&self, state: &mut $crate::cpu::save_state::StateWriter) {
+          state.write(&(*self as $crate::consts::Byte));
+        }
+
+        fn read(
+          state: &mut $crate::cpu::save_state::StateReader,
+        ) -> Result<Self, $crate::cpu::save_state::SaveStateError> {
+          Self::from_repr(state.read()?).ok_or(
+            $crate::cpu::save_state::SaveStateError::InvalidValue(stringify!($ty)),
+          )
+        }
+      }
+    )+
+  };
+}
+pub(crate) use enum_state_field;
+
+enum_state_field!(ChipVariant, ExecutionMode);
+
+impl CPU {
+  /// Serializes the whole CPU, including progress of the instruction being executed.
+  pub fn save_state(&self) -> Vec<Byte> {
+    let mut state = StateWriter::default();
+    state.push(&MAGIC);
+    state.write(&VERSION);
+
+    state.write(&self.chip_variant);
+    state.write(&self.cycle);
+    state.write(&self.program_counter);
+    state.write(&self.stack_pointer);
+    state.write(&self.accumulator);
+    state.write(&self.index_register_x);
+    state.write(&self.index_register_y);
+    state.write(&Byte::from(self.processor_status));
+    state.write(&self.sync);
+    state.write(&self.irq);
+    state.write(&self.nmi);
+    state.write(&self.nmi_pending);
+    state.write(&self.reset_vector);
+    state.write(&self.brk_vector);
+    state.write(&self.nmi_vector);
+    state.write(&self.execution_mode);
+    state.write(&self.addr);
+
+    state.write(&self.current_instruction.is_some());
+    if let Some(instruction) = &self.current_instruction {
+      state.write(&instruction.opcode);
+      state.write(&instruction.addr);
+      state.write(&instruction.starting_cycle);
+      instruction.tasks.save(&mut state);
+    }
+
+    state.bytes
+  }
+
+  /// Replaces the CPU state with one produced by `save_state`. On error the CPU is left
+  /// unchanged.
+  pub fn load_state(&mut self, bytes: &[Byte]) -> Result<(), SaveStateError> {
+    let mut state = StateReader { bytes };
+    if state.take::<4>()? != MAGIC {
+      return Err(SaveStateError::InvalidMagic);
+    }
+    let version: Byte = state.read()?;
+    if version != VERSION {
+      return Err(SaveStateError::UnsupportedVersion(version));
+    }
+
+    let mut cpu = CPU::new(state.read()?);
+    cpu.cycle = state.read()?;
+    cpu.program_counter = state.read()?;
+    cpu.stack_pointer = state.read()?;
+    cpu.accumulator = state.read()?;
+    cpu.index_register_x = state.read()?;
+    cpu.index_register_y = state.read()?;
+    cpu.processor_status.set(state.read()?);
+    cpu.sync = state.read()?;
+    cpu.irq = state.read()?;
+    cpu.nmi = state.read()?;
+    cpu.nmi_pending = state.read()?;
+    cpu.reset_vector = state.read()?;
+    cpu.brk_vector = state.read()?;
+    cpu.nmi_vector = state.read()?;
+    cpu.execution_mode = state.read()?;
+    let addr = state.read()?;
+
+    if state.read::<bool>()? {
+      let opcode: Byte = state.read()?;
+      let instruction = INSTRUCTIONS[opcode as usize]
+        .as_ref()
+        .ok_or(SaveStateError::InvalidValue("opcode"))?;
+      let instruction_addr = state.read()?;
+      let starting_cycle = state.read()?;
+
+      // handlers may depend on registers, so tasks are rebuilt before their progress is restored
+      let mut tasks = (instruction.handler)(&mut cpu);
+      tasks.restore(&mut state)?;
+      let name = match &tasks {
+        InstructionTasks::Brk(tasks) => tasks.interrupt().name(),
+        _ => instruction.name,
+      };
+      cpu.current_instruction = Some(InstructionExecution {
+        addr: instruction_addr,
+        tasks,
+        opcode,
+        name,
+        starting_cycle,
+      });
+    }
+    cpu.addr = addr;
+
+    if !state.bytes.is_empty() {
+      return Err(SaveStateError::TrailingData);
+    }
+
+    *self = cpu;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    consts::Word,
+    cpu::CPU,
+    memory::{Generic64kMem, Operation},
+  };
+
+  // Bus accesses of each cycle, used to compare executions.
+  fn trace(cpu: &mut CPU, memory: &mut Generic64kMem, cycles: usize) -> Vec<(bool, Word)> {
+    (0..cycles)
+      .map(|_| {
+        cpu.tick(memory);
+        match memory.get_last_operation() {
+          Some(Operation::Read(addr)) => (false, addr),
+          Some(Operation::Write(addr)) => (true, addr),
+          None => (false, 0),
+        }
+      })
+      .collect()
+  }
+
+  fn program() -> Generic64kMem {
+    let mut memory = Generic64kMem::new();
+    // JSR $0610, INC $0200,X, BRK ... $0610: LDA ($80),Y, PHA, ROR $0201, RTS
+    memory.insert(0x0600, &[0x20, 0x10, 0x06, 0xFE, 0xFF, 0x01, 0x00]);
+    memory.insert(0x0610, &[0xB1, 0x80, 0x48, 0x6E, 0x01, 0x02, 0x60]);
+    memory.insert(0x0080, &[0xF0, 0x01]);
+    memory.insert(0x0200, &[0x81, 0x42]);
+    memory
+  }
+
+  fn cpu() -> CPU {
+    let mut cpu = CPU::new_nmos();
+    cpu.program_counter = 0x0600;
+    cpu.stack_pointer = 0xFF;
+    cpu.index_register_x = 0x02;
+    cpu.index_register_y = 0x20;
+    cpu
+  }
+
+  #[cfg(test)]
+  mod load_state {
+    use super::{cpu, program, trace};
+    use crate::cpu::{CPU, save_state::SaveStateError};
+
+    #[test]
+    fn should_continue_with_identical_bus_trace_after_restore_at_any_cycle() {
+      let mut reference_memory = program();
+      let mut reference = cpu();
+      let expected = trace(&mut reference, &mut reference_memory, 40);
+
+      for split in 0..40 {
+        let mut memory = program();
+        let mut original = cpu();
+        let mut actual = trace(&mut original, &mut memory, split);
+
+        let mut restored = CPU::new_wdc_cmos();
+        restored.load_state(&original.save_state()).unwrap();
+        actual.extend(trace(&mut restored, &mut memory, 40 - split));
+
+        assert_eq!(actual, expected, "restored after cycle {split}");
+        assert_eq!(restored.state(), reference.state());
+      }
+    }
+
+    #[test]
+    fn should_reject_invalid_save_states() {
+      let mut uut = cpu();
+      let mut state = uut.save_state();
+
+      assert_eq!(uut.load_state(b"NOPE"), Err(SaveStateError::InvalidMagic));
+      assert_eq!(
+        uut.load_state(&state[..state.len() - 1]),
+        Err(SaveStateError::UnexpectedEnd)
+      );
+      state[4] = 0xFF;
+      assert_eq!(
+        uut.load_state(&state),
+        Err(SaveStateError::UnsupportedVersion(0xFF))
+      );
+    }
+  }
+}
@@ -0,0 +1,236 @@
+use crate::{cpu::CPU, memory::Memory};
+
+/// Clock of a CPU relative to the master clock of a `Scheduler`: `cycles` CPU cycles are run
+/// every `per_master_cycles` master cycles.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockRatio {
+  pub cycles: u32,
+  pub per_master_cycles: u32,
+}
+
+impl ClockRatio {
+  pub fn new(cycles: u32, per_master_cycles: u32) -> Self {
+    assert!(per_master_cycles > 0, "master cycles of a ratio can't be 0");
+    ClockRatio {
+      cycles,
+      per_master_cycles,
+    }
+  }
+}
+
+impl Default for ClockRatio {
+  fn default() -> Self {
+    ClockRatio::new(1, 1)
+  }
+}
+
+/// Index of a CPU in its `Scheduler`, in the order of `add_cpu` calls.
+pub type CpuId = usize;
+
+/// Cycle of a CPU placed on the global timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelineEntry {
+  pub master_cycle: u64,
+  pub cpu: CpuId,
+  /// Cycle count of the CPU after the tick.
+  pub cycle: usize,
+}
+
+struct ScheduledCpu<M> {
+  cpu: CPU,
+  memory: M,
+  ratio: ClockRatio,
+  // CPU cycles owed, in units of 1 / ratio.per_master_cycles
+  accumulated: u64,
+}
+
+/// Runs several CPUs in lockstep against a master clock. CPUs sharing memory do it through
+/// regions mapped into each of their memories, like `SharedRam`.
+///
+/// Within a master cycle CPUs are ticked in the order they were added, a CPU running several
+/// cycles per master cycle runs them all before the next CPU. Writes are ended with each cycle,
+/// so CPUs ticked later see them in the same master cycle.
+pub struct Scheduler<M: Memory> {
+  cpus: Vec<ScheduledCpu<M>>,
+  master_cycle: u64,
+}
+
+impl<M: Memory> Default for Scheduler<M> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<M: Memory> Scheduler<M> {
+  pub fn new() -> Self {
+    Scheduler {
+      cpus: Vec::new(),
+      master_cycle: 0,
+    }
+  }
+
+  pub fn add_cpu(&mut self, cpu: CPU, memory: M, ratio: ClockRatio) -> CpuId {
+    self.cpus.push(ScheduledCpu {
+      cpu,
+      memory,
+      ratio,
+      accumulated: 0,
+    });
+    self.cpus.len() - 1
+  }
+
+  pub fn master_cycle(&self) -> u64 {
+    self.master_cycle
+  }
+
+  pub fn cpu(&self, id: CpuId) -> &CPU {
+    &self.cpus[id].cpu
+  }
+
+  pub fn cpu_mut(&mut self, id: CpuId) -> &mut CPU {
+    &mut self.cpus[id].cpu
+  }
+
+  pub fn memory(&self, id: CpuId) -> &M {
+    &self.cpus[id].memory
+  }
+
+  pub fn memory_mut(&mut self, id: CpuId) -> &mut M {
+    &mut self.cpus[id].memory
+  }
+
+  /// Advances the master clock by one cycle.
+  pub fn tick(&mut self) {
+    self.tick_with(|_, _, _| {});
+  }
+
+  /// Advances the master clock by one cycle and passes every CPU cycle run to `observer`, in
+  /// order, e.g. to probe a `Debugger` per CPU.
+  pub fn tick_with<O: FnMut(TimelineEntry, &CPU, &M)>(&mut self, mut observer: O) {
+    for (id, scheduled) in self.cpus.iter_mut().enumerate() {
+      scheduled.accumulated += scheduled.ratio.cycles as u64;
+      while scheduled.accumulated >= scheduled.ratio.per_master_cycles as u64 {
+        scheduled.accumulated -= scheduled.ratio.per_master_cycles as u64;
+        scheduled.cpu.tick(&mut scheduled.memory);
+
+        let entry = TimelineEntry {
+          master_cycle: self.master_cycle,
+          cpu: id,
+          cycle: scheduled.cpu.cycle,
+        };
+        observer(entry, &scheduled.cpu, &scheduled.memory);
+      }
+    }
+
+    self.master_cycle += 1;
+  }
+
+  pub fn run_for_cycles(&mut self, master_cycles: u64) {
+    self.run_for_cycles_with(master_cycles, |_, _, _| {});
+  }
+
+  pub fn run_for_cycles_with<O: FnMut(TimelineEntry, &CPU, &M)>(
+    &mut self,
+    master_cycles: u64,
+    mut observer: O,
+  ) {
+    for _ in 0..master_cycles {
+      self.tick_with(&mut observer);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    consts::Word,
+    cpu::CPU,
+    memory::{Generic64kMem, Memory, map::MemoryMap, shared_ram::SharedRam},
+  };
+
+  fn cpu_at(pc: Word) -> CPU {
+    let mut cpu = CPU::new_nmos();
+    let mut state = cpu.state();
+    state.pc = pc;
+    cpu.set_state(state);
+    cpu
+  }
+
+  #[cfg(test)]
+  mod tick_with {
+    use crate::{
+      cpu::scheduler::{ClockRatio, Scheduler, TimelineEntry},
+      memory::Generic64kMem,
+    };
+
+    use super::cpu_at;
+
+    #[test]
+    fn should_run_cpus_at_their_clock_ratios_in_order() {
+      let mut scheduler = Scheduler::new();
+      let fast = scheduler.add_cpu(cpu_at(0x0000), Generic64kMem::new(), ClockRatio::new(2, 1));
+      let slow = scheduler.add_cpu(cpu_at(0x0000), Generic64kMem::new(), ClockRatio::new(1, 3));
+
+      let mut timeline = Vec::new();
+      scheduler.run_for_cycles_with(4, |entry, _, _| timeline.push(entry));
+
+      let entry = |master_cycle, cpu, cycle| TimelineEntry {
+        master_cycle,
+        cpu,
+        cycle,
+      };
+      assert_eq!(
+        timeline,
+        [
+          entry(0, fast, 1),
+          entry(0, fast, 2),
+          entry(1, fast, 3),
+          entry(1, fast, 4),
+          entry(2, fast, 5),
+          entry(2, fast, 6),
+          entry(2, slow, 1),
+          entry(3, fast, 7),
+          entry(3, fast, 8),
+        ]
+      );
+      assert_eq!(scheduler.master_cycle(), 4);
+    }
+  }
+
+  #[cfg(test)]
+  mod run_for_cycles {
+    use crate::cpu::scheduler::{ClockRatio, Scheduler};
+
+    use super::*;
+
+    #[test]
+    fn should_let_cpus_communicate_through_shared_memory() {
+      let shared = SharedRam::new(0x100);
+      let mut writer_memory = Generic64kMem::new();
+      // LDA #$42, STA $1000, JMP $0005
+      writer_memory.insert(0x0000, &[0xA9, 0x42, 0x8D, 0x00, 0x10, 0x4C, 0x05, 0x00]);
+      let mut writer = MemoryMap::new();
+      writer.map(0x0000..=0xFFFF, writer_memory);
+      writer.map(0x1000..=0x10FF, shared.clone());
+
+      let mut reader_memory = Generic64kMem::new();
+      // loop: LDX $2000, BEQ loop, STX $0300, JMP $0008
+      reader_memory.insert(
+        0x0000,
+        &[
+          0xAE, 0x00, 0x20, 0xF0, 0xFB, 0x8E, 0x00, 0x03, 0x4C, 0x08, 0x00,
+        ],
+      );
+      let mut reader = MemoryMap::new();
+      reader.map(0x0000..=0xFFFF, reader_memory);
+      reader.map(0x2000..=0x20FF, shared.clone());
+
+      let mut scheduler = Scheduler::new();
+      scheduler.add_cpu(cpu_at(0x0000), writer, ClockRatio::default());
+      let reader = scheduler.add_cpu(cpu_at(0x0000), reader, ClockRatio::new(1, 2));
+      scheduler.run_for_cycles(60);
+
+      assert_eq!(scheduler.memory(reader).peek(0x0300), 0x42);
+    }
+  }
+}
@@ -1,6 +1,7 @@
 use crate::memory::Memory;
 
 use super::CPU;
+use super::save_state::{SaveStateError, StateReader, StateWriter};
 
 pub mod modify_memory;
 pub mod modify_register;
@@ -10,4 +11,57 @@ pub mod transfer_register;
 pub trait Tasks {
   fn done(&self) -> bool;
   fn tick(&mut self, cpu: &mut CPU, memory: &mut dyn Memory) -> bool;
+  /// Writes the progress of the tasks, see `CPU::save_state`.
+  fn save(&self, state: &mut StateWriter);
+  /// Restores progress written by `save` into tasks created by the same instruction handler.
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError>;
 }
+
+/// Declares an enum wrapping task types and implements `Tasks` for it by dispatching to the
+/// wrapped tasks, so that tasks can be held without allocating or dynamic dispatch.
+macro_rules! tasks_enum {
+  ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident($ty:ty)),+ $(,)? }) => {
+    $(#[$meta])*
+    $vis enum $name {
+      $($variant($ty)),+
+    }
+
+    $(
+      impl From<$ty> for $name {
+        fn from(tasks: $ty) -> Self {
+          $name::$variant(tasks)
+        }
+      }
+    )+
+
+    impl $crate::cpu::tasks::Tasks for $name {
+      fn done(&self) -> bool {
+        match self {
+          $($name::$variant(tasks) => tasks.done()),+
+        }
+      }
+
+      fn tick(&mut self, cpu: &mut $crate::cpu::CPU, memory: &mut dyn $crate::memory::Memory) -> bool {
+        match self {
+          $($name::$variant(tasks) => tasks.tick(cpu, memory)),+
+        }
+      }
+
+      fn save(&self, state: &mut $crate::cpu::save_state::StateWriter) {
+        match self {
+          $($name::$variant(tasks) => tasks.save(state)),+
+        }
+      }
+
+      fn restore(
+        &mut self,
+        state: &mut $crate::cpu::save_state::StateReader,
+      ) -> Result<(), $crate::cpu::save_state::SaveStateError> {
+        match self {
+          $($name::$variant(tasks) => tasks.restore(state)),+
+        }
+      }
+    }
+  };
+}
+pub(crate) use tasks_enum;
@@ -1,11 +1,18 @@
+use strum::FromRepr;
+
 use crate::{
   consts::Byte,
-  cpu::{CPU, addressing::AddressingTasks},
+  cpu::{
+    CPU,
+    addressing::{AddressingModeTasks, AddressingTasks},
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
+  },
   memory::Memory,
 };
 
 use super::Tasks;
 
+#[derive(Clone)]
 enum ModificationVariant {
   Inc,
   Dec,
@@ -15,7 +22,8 @@ enum ModificationVariant {
   RotateRight,
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum ModifyMemoryStep {
   Addressing,
   MemoryAccess,
@@ -24,16 +32,19 @@ enum ModifyMemoryStep {
   Done,
 }
 
+enum_state_field!(ModifyMemoryStep);
+
+#[derive(Clone)]
 pub struct ModifyMemoryTasks {
   variant: ModificationVariant,
-  addr_tasks: Box<dyn AddressingTasks>,
+  addr_tasks: AddressingModeTasks,
   step: ModifyMemoryStep,
   previous_value: Byte,
   value: Byte,
 }
 
 impl ModifyMemoryTasks {
-  pub fn new_inc(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_inc(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::Inc,
       addr_tasks,
@@ -43,7 +54,7 @@ impl ModifyMemoryTasks {
     }
   }
 
-  pub fn new_dec(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_dec(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::Dec,
       addr_tasks,
@@ -53,7 +64,7 @@ impl ModifyMemoryTasks {
     }
   }
 
-  pub fn new_shift_left(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_shift_left(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::ShiftLeft,
       addr_tasks,
@@ -63,7 +74,7 @@ impl ModifyMemoryTasks {
     }
   }
 
-  pub fn new_shift_right(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_shift_right(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::ShiftRight,
       addr_tasks,
@@ -73,7 +84,7 @@ impl ModifyMemoryTasks {
     }
   }
 
-  pub fn new_rotate_left(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_rotate_left(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::RotateLeft,
       addr_tasks,
@@ -83,7 +94,7 @@ impl ModifyMemoryTasks {
     }
   }
 
-  pub fn new_rotate_right(addr_tasks: Box<dyn AddressingTasks>) -> Self {
+  pub fn new_rotate_right(addr_tasks: AddressingModeTasks) -> Self {
     ModifyMemoryTasks {
       variant: ModificationVariant::RotateRight,
       addr_tasks,
@@ -195,4 +206,19 @@ impl Tasks for ModifyMemoryTasks {
       }
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    self.addr_tasks.save(state);
+    state.write(&self.step);
+    state.write(&self.previous_value);
+    state.write(&self.value);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.addr_tasks.restore(state)?;
+    self.step = state.read()?;
+    self.previous_value = state.read()?;
+    self.value = state.read()?;
+    Ok(())
+  }
 }
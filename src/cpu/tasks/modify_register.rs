@@ -1,11 +1,15 @@
 use crate::{
   consts::Byte,
-  cpu::{CPU, Registers},
+  cpu::{
+    CPU, Registers,
+    save_state::{SaveStateError, StateReader, StateWriter},
+  },
   memory::Memory,
 };
 
 use super::Tasks;
 
+#[derive(Clone)]
 enum ModificationVariant {
   Inc,
   Dec,
@@ -15,6 +19,7 @@ enum ModificationVariant {
   RotateRight,
 }
 
+#[derive(Clone)]
 pub struct ModifyRegisterTasks {
   variant: ModificationVariant,
   register: Registers,
@@ -137,4 +142,15 @@ impl Tasks for ModifyRegisterTasks {
     self.done = true;
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.value);
+    state.write(&self.done);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.value = state.read()?;
+    self.done = state.read()?;
+    Ok(())
+  }
 }
@@ -1,8 +1,11 @@
+use strum::FromRepr;
+
 use crate::{
   consts::Byte,
   cpu::{
     CPU,
-    addressing::{AddressingMode, AddressingTasks},
+    addressing::{AddressingMode, AddressingModeTasks, AddressingTasks},
+    save_state::{SaveStateError, StateReader, StateWriter, enum_state_field},
   },
   memory::Memory,
 };
@@ -13,22 +16,26 @@ pub trait ReadMemoryTasks: Tasks {
   fn value(&self) -> Option<Byte>;
 }
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, FromRepr)]
+#[repr(u8)]
 enum AddressingReadMemoryStep {
   AddressCalculation,
   SeparateMemoryAccess,
   Done,
 }
 
+enum_state_field!(AddressingReadMemoryStep);
+
+#[derive(Clone)]
 pub struct AddressingReadMemoryTasks {
-  addressing_tasks: Box<dyn Tasks>,
+  addressing_tasks: AddressingModeTasks,
   access_during_addressing: bool,
   step: AddressingReadMemoryStep,
   value: Option<Byte>,
 }
 
 impl AddressingReadMemoryTasks {
-  pub fn new_with_access_during_addressing(addressing_tasks: Box<dyn Tasks>) -> Self {
+  pub fn new_with_access_during_addressing(addressing_tasks: AddressingModeTasks) -> Self {
     AddressingReadMemoryTasks {
       addressing_tasks,
       access_during_addressing: true,
@@ -37,7 +44,7 @@ impl AddressingReadMemoryTasks {
     }
   }
 
-  pub fn new_with_access_in_separate_cycle(addressing_tasks: Box<dyn Tasks>) -> Self {
+  pub fn new_with_access_in_separate_cycle(addressing_tasks: AddressingModeTasks) -> Self {
     AddressingReadMemoryTasks {
       addressing_tasks,
       access_during_addressing: false,
@@ -99,8 +106,22 @@ impl Tasks for AddressingReadMemoryTasks {
       AddressingReadMemoryStep::Done => true,
     }
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    self.addressing_tasks.save(state);
+    state.write(&self.step);
+    state.write(&self.value);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.addressing_tasks.restore(state)?;
+    self.step = state.read()?;
+    self.value = state.read()?;
+    Ok(())
+  }
 }
 
+#[derive(Clone)]
 pub struct ImmediateReadMemoryTasks {
   done: bool,
   value: Option<Byte>,
@@ -140,6 +161,17 @@ impl Tasks for ImmediateReadMemoryTasks {
 
     true
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+    state.write(&self.value);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    self.value = state.read()?;
+    Ok(())
+  }
 }
 
 impl ReadMemoryTasks for ImmediateReadMemoryTasks {
@@ -1,8 +1,12 @@
 use crate::{
-  cpu::{CPU, Registers, Tasks},
+  cpu::{
+    CPU, Registers, Tasks,
+    save_state::{SaveStateError, StateReader, StateWriter},
+  },
   memory::Memory,
 };
 
+#[derive(Clone)]
 pub struct TransferRegistersTasks {
   src: Registers,
   tgt: Registers,
@@ -35,4 +39,13 @@ impl Tasks for TransferRegistersTasks {
     self.done = true;
     self.done
   }
+
+  fn save(&self, state: &mut StateWriter) {
+    state.write(&self.done);
+  }
+
+  fn restore(&mut self, state: &mut StateReader) -> Result<(), SaveStateError> {
+    self.done = state.read()?;
+    Ok(())
+  }
 }
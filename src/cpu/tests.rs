@@ -451,6 +451,403 @@ mod sync {
   }
 }
 
+#[cfg(test)]
+mod state {
+  use crate::cpu::tests::MemoryMock;
+
+  use super::super::*;
+
+  #[test]
+  fn should_return_registers_cycle_and_variant() {
+    let mut memory = MemoryMock::new(&[0xA9, 0x42]);
+    let mut uut = CPU::new_wdc_cmos();
+    uut.program_counter = 0x0000;
+    uut.stack_pointer = 0xFD;
+
+    uut.step(&mut memory);
+
+    assert_eq!(
+      uut.state(),
+      CpuState {
+        pc: 0x0002,
+        sp: 0xFD,
+        a: 0x42,
+        x: 0x00,
+        y: 0x00,
+        p: 0b00100000,
+        cycle: 2,
+        variant: ChipVariant::WDCCMOS
+      }
+    );
+  }
+
+  #[test]
+  fn should_restore_state_set_by_host() {
+    let mut memory = MemoryMock::new(&[0x8A]); // TXA
+    let mut uut = CPU::new_nmos();
+    let state = CpuState {
+      pc: 0x0000,
+      sp: 0xF0,
+      a: 0x00,
+      x: 0x80,
+      y: 0x11,
+      p: 0b00000001,
+      cycle: 100,
+      variant: ChipVariant::NMOS,
+    };
+
+    uut.set_state(state);
+    assert_eq!(
+      uut.state(),
+      CpuState {
+        p: 0b00100001,
+        ..state
+      }
+    );
+
+    uut.step(&mut memory);
+
+    assert_eq!(uut.state().a, 0x80);
+    assert_eq!(uut.state().p, 0b10100001);
+    assert_eq!(uut.state().cycle, 102);
+  }
+}
+
+#[cfg(test)]
+mod step {
+  use crate::cpu::tests::MemoryMock;
+
+  use super::super::*;
+
+  #[test]
+  fn should_execute_whole_instruction_and_report_its_cycles() {
+    // LDA $0004, NOP
+    let mut memory = MemoryMock::new(&[0xAD, 0x04, 0x00, 0xEA, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+
+    let result = uut.step(&mut memory);
+
+    assert_eq!(
+      result,
+      RunResult {
+        cycles: 4,
+        stop_reason: StopReason::InstructionDone
+      }
+    );
+    assert_eq!(uut.accumulator, 0x42);
+    assert_eq!(uut.program_counter, 0x0003);
+  }
+
+  #[test]
+  fn should_finish_current_instruction_when_called_mid_instruction() {
+    let mut memory = MemoryMock::new(&[0xAD, 0x04, 0x00, 0xEA, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+    uut.tick(&mut memory);
+
+    let result = uut.step(&mut memory);
+
+    assert_eq!(result.cycles, 3);
+    assert_eq!(uut.program_counter, 0x0003);
+  }
+
+  #[test]
+  fn should_execute_whole_instruction_at_once_in_instruction_mode() {
+    let mut memory = MemoryMock::new(&[0xAD, 0x04, 0x00, 0xEA, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+    uut.set_execution_mode(ExecutionMode::Instruction);
+
+    let result = uut.step(&mut memory);
+
+    assert_eq!(
+      result,
+      RunResult {
+        cycles: 4,
+        stop_reason: StopReason::InstructionDone
+      }
+    );
+    assert!(uut.current_instruction.is_none());
+    assert_eq!(uut.accumulator, 0x42);
+    assert_eq!(uut.program_counter, 0x0003);
+  }
+
+  #[test]
+  fn should_finish_ticked_instruction_before_switching_to_instruction_mode() {
+    let mut memory = MemoryMock::new(&[0xAD, 0x04, 0x00, 0xEA, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+    uut.tick(&mut memory);
+    uut.set_execution_mode(ExecutionMode::Instruction);
+
+    let result = uut.step(&mut memory);
+
+    assert_eq!(result.cycles, 3);
+    assert_eq!(uut.accumulator, 0x42);
+
+    let result = uut.step(&mut memory);
+
+    assert_eq!(result.cycles, 2);
+    assert_eq!(uut.program_counter, 0x0004);
+  }
+}
+
+#[cfg(test)]
+mod run_for_cycles {
+  use crate::cpu::tests::MemoryMock;
+
+  use super::super::*;
+
+  #[test]
+  fn should_stop_on_exact_cycle_budget_even_mid_instruction() {
+    let mut memory = MemoryMock::new(&[0xEA, 0xAD, 0x04, 0x00, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+
+    let result = uut.run_for_cycles(&mut memory, 3);
+
+    assert_eq!(
+      result,
+      RunResult {
+        cycles: 3,
+        stop_reason: StopReason::CycleBudget
+      }
+    );
+    assert!(uut.current_instruction.is_some());
+    assert_eq!(uut.accumulator, 0x00);
+
+    uut.run_for_cycles(&mut memory, 3);
+
+    assert!(uut.current_instruction.is_none());
+    assert_eq!(uut.accumulator, 0x42);
+  }
+
+  #[test]
+  fn should_stop_on_exact_cycle_budget_in_instruction_mode() {
+    let mut memory = MemoryMock::new(&[0xEA, 0xAD, 0x04, 0x00, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+    uut.set_execution_mode(ExecutionMode::Instruction);
+
+    let result = uut.run_for_cycles(&mut memory, 3);
+
+    assert_eq!(
+      result,
+      RunResult {
+        cycles: 3,
+        stop_reason: StopReason::CycleBudget
+      }
+    );
+    assert!(uut.current_instruction.is_some());
+    assert_eq!(uut.accumulator, 0x00);
+
+    let result = uut.run_for_cycles(&mut memory, 3);
+
+    assert_eq!(result.cycles, 3);
+    assert!(uut.current_instruction.is_none());
+    assert_eq!(uut.accumulator, 0x42);
+  }
+
+  #[test]
+  fn should_end_in_same_state_in_both_execution_modes() {
+    let [cycle, instruction] = [ExecutionMode::Cycle, ExecutionMode::Instruction].map(|mode| {
+      // loop: INX, INC $10, JMP $0000
+      let mut memory = MemoryMock::new(&[0xE8, 0xEE, 0x10, 0x00, 0x4C, 0x00, 0x00]);
+      let mut uut = CPU::new_nmos();
+      uut.program_counter = 0x0000;
+      uut.set_execution_mode(mode);
+
+      let result = uut.run_for_cycles(&mut memory, 1000);
+      (result, uut.state(), memory[0x10])
+    });
+
+    assert_eq!(cycle, instruction);
+  }
+}
+
+#[cfg(test)]
+mod interrupts {
+  use crate::memory::Generic64kMem;
+
+  use super::super::*;
+
+  fn setup(mode: ExecutionMode) -> (CPU, Generic64kMem) {
+    let mut memory = Generic64kMem::new();
+    // NOP, NOP, and a NOP as NMI handler
+    memory.insert(0x0200, &[0xEA, 0xEA]);
+    memory.insert(0x0300, &[0xEA]);
+    memory.insert(0xFFFA, &[0x00, 0x03, 0x00, 0x02, 0x00, 0x04]);
+    let mut uut = CPU::new_wdc_cmos();
+    uut.program_counter = 0x0200;
+    uut.stack_pointer = 0xFF;
+    uut.processor_status.set(0b0010_1000);
+    uut.set_execution_mode(mode);
+    (uut, memory)
+  }
+
+  #[test]
+  fn should_take_irq_in_seven_cycles_in_both_execution_modes() {
+    for mode in [ExecutionMode::Cycle, ExecutionMode::Instruction] {
+      let (mut uut, mut memory) = setup(mode);
+      uut.set_irq(true);
+
+      let result = uut.step(&mut memory);
+
+      assert_eq!(result.cycles, 7, "{mode:?}");
+      assert_eq!(uut.program_counter, 0x0400, "{mode:?}");
+      assert_eq!(uut.stack_pointer, 0xFC, "{mode:?}");
+      assert_eq!(memory.data[0x01FF], 0x02, "{mode:?}");
+      assert_eq!(memory.data[0x01FE], 0x00, "{mode:?}");
+      assert_eq!(memory.data[0x01FD], 0b0010_1000, "{mode:?}");
+      assert!(
+        uut.processor_status.get_interrupt_disable_flag(),
+        "{mode:?}"
+      );
+      assert!(!uut.processor_status.get_decimal_mode_flag(), "{mode:?}");
+    }
+  }
+
+  #[test]
+  fn should_not_take_irq_when_interrupts_are_disabled() {
+    let (mut uut, mut memory) = setup(ExecutionMode::Cycle);
+    uut.processor_status.change_interrupt_disable_flag(true);
+    uut.set_irq(true);
+
+    uut.step(&mut memory);
+
+    assert_eq!(uut.program_counter, 0x0201);
+  }
+
+  #[test]
+  fn should_take_nmi_only_on_asserting_edge() {
+    let (mut uut, mut memory) = setup(ExecutionMode::Instruction);
+    uut.processor_status.change_interrupt_disable_flag(true);
+    uut.set_nmi(true);
+
+    uut.step(&mut memory);
+    assert_eq!(uut.program_counter, 0x0300);
+
+    uut.set_nmi(true);
+    uut.step(&mut memory);
+    assert_eq!(uut.program_counter, 0x0301);
+  }
+
+  #[test]
+  fn should_keep_interrupt_in_progress_in_save_state() {
+    let (mut uut, mut memory) = setup(ExecutionMode::Cycle);
+    uut.set_nmi(true);
+    uut.run_for_cycles(&mut memory, 3);
+
+    let mut restored = CPU::new_nmos();
+    restored.load_state(&uut.save_state()).unwrap();
+
+    assert_eq!(restored.current_instruction.as_ref().unwrap().name, "NMI");
+    restored.step(&mut memory);
+    assert_eq!(restored.program_counter, 0x0300);
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use crate::memory::Generic64kMem;
+
+  use super::super::*;
+
+  #[test]
+  fn should_continue_exactly_like_original() {
+    let mut memory = Generic64kMem::new();
+    // loop: INC $10, LDA ($20),Y, JSR $0300, JMP $0200
+    memory.insert(
+      0x0200,
+      &[0xE6, 0x10, 0xB1, 0x20, 0x20, 0x00, 0x03, 0x4C, 0x00, 0x02],
+    );
+    // PHA, ROR $10, PLA, RTS
+    memory.insert(0x0300, &[0x48, 0x66, 0x10, 0x68, 0x60]);
+    memory.insert(0x0020, &[0xF0, 0x04]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0200;
+    uut.stack_pointer = 0xFF;
+    uut.index_register_y = 0x20;
+    uut.run_for_cycles(&mut memory, 9);
+    assert!(uut.current_instruction.is_some());
+
+    let mut fork = uut.clone();
+    let mut fork_memory = Generic64kMem::new();
+    fork_memory.data = memory.data.clone();
+
+    for _ in 0..200 {
+      uut.tick(&mut memory);
+      fork.tick(&mut fork_memory);
+
+      assert_eq!(fork.save_state(), uut.save_state());
+      assert!(fork_memory.data == memory.data);
+    }
+  }
+}
+
+#[cfg(test)]
+mod send {
+  use crate::{cpu::debugger::Debugger, memory::Generic64kMem};
+
+  use super::super::*;
+
+  #[test]
+  fn should_continue_instruction_on_another_thread() {
+    let mut memory = Generic64kMem::new();
+    memory.insert(0x0000, &[0xAD, 0x04, 0x00, 0xEA, 0x42]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+    uut.tick(&mut memory);
+    let mut debugger = Debugger::new();
+
+    let uut = std::thread::spawn(move || {
+      uut.step(&mut memory);
+      debugger.probe(&uut, &memory);
+      uut
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(uut.accumulator, 0x42);
+    assert_eq!(uut.program_counter, 0x0003);
+  }
+}
+
+#[cfg(test)]
+mod run_until {
+  use crate::cpu::tests::MemoryMock;
+
+  use super::super::*;
+
+  #[test]
+  fn should_run_whole_instructions_until_predicate_holds() {
+    let mut memory = MemoryMock::new(&[0xEA, 0xEA, 0xEA, 0xEA]);
+    let mut uut = CPU::new_nmos();
+    uut.program_counter = 0x0000;
+
+    let result = uut.run_until(&mut memory, |cpu| cpu.program_counter == 0x0003);
+
+    assert_eq!(
+      result,
+      RunResult {
+        cycles: 6,
+        stop_reason: StopReason::Predicate
+      }
+    );
+  }
+
+  #[test]
+  fn should_not_run_when_predicate_holds_already() {
+    let mut memory = MemoryMock::new(&[0xEA]);
+    let mut uut = CPU::new_nmos();
+
+    let result = uut.run_until(&mut memory, |_| true);
+
+    assert_eq!(result.cycles, 0);
+  }
+}
+
 #[cfg(test)]
 pub fn run_tasks(cpu: &mut super::CPU, tasks: &mut dyn super::Tasks, memory: &mut dyn Memory) {
   while !tasks.done() {
@@ -5,8 +5,8 @@ use serde::de::Error;
 use serde::{Deserialize, Deserializer};
 
 use crate::consts::{Byte, Word};
-use crate::cpu::CPU;
 use crate::cpu::processor_status::ProcessorStatus;
+use crate::cpu::{CPU, ExecutionMode};
 
 use crate::memory::Generic64kMem;
 
@@ -143,6 +143,82 @@ fn nmos6502_tests() {
   }
 }
 
+#[test]
+#[ignore = "takes a long time to finish"]
+fn nmos6502_instruction_mode_tests() {
+  for i in LEGAL_OPCODES {
+    let filename = format!("{i:02x}.json");
+    let specs = load_spec(&filename);
+
+    for spec in specs {
+      if DECIMAL_DEPENDENT_OPCODES.contains(&i) && spec.initial_status.p & 0b00001000 > 1 {
+        continue;
+      }
+
+      let [stepped, interpreted] = [ExecutionMode::Cycle, ExecutionMode::Instruction].map(|mode| {
+        let mut uut = CPU::new_nmos();
+        let mut memory = Generic64kMem::new();
+        uut.set_execution_mode(mode);
+        uut.processor_status.set(spec.initial_status.p);
+        uut.accumulator = spec.initial_status.a;
+        uut.index_register_x = spec.initial_status.x;
+        uut.index_register_y = spec.initial_status.y;
+        uut.stack_pointer = spec.initial_status.s;
+        uut.program_counter = spec.initial_status.pc;
+        for [addr, val] in &spec.initial_status.ram {
+          memory[*addr] = *val as u8;
+        }
+
+        let result = uut.step(&mut memory);
+        (uut.state(), result.cycles, memory.data)
+      });
+      let context = format!("for test \"{}\" in file \"{filename}\"", &spec.name);
+
+      assert_eq!(
+        (stepped.0, stepped.1),
+        (interpreted.0, interpreted.1),
+        "state mismatch {context}"
+      );
+      assert!(stepped.2 == interpreted.2, "memory mismatch {context}");
+      assert_eq!(
+        interpreted.1,
+        spec.cycles.len(),
+        "cycles mismatch {context}"
+      );
+      assert_eq!(
+        (
+          interpreted.0.pc,
+          interpreted.0.a,
+          interpreted.0.x,
+          interpreted.0.y
+        ),
+        (
+          spec.final_status.pc,
+          spec.final_status.a,
+          spec.final_status.x,
+          spec.final_status.y
+        ),
+        "registers mismatch {context}"
+      );
+      assert_eq!(
+        interpreted.0.sp, spec.final_status.s,
+        "stack pointer mismatch {context}"
+      );
+      assert_eq!(
+        interpreted.0.p,
+        Byte::from(ProcessorStatus::from(spec.final_status.p)),
+        "processor status mismatch {context}"
+      );
+      for [addr, expected_val] in &spec.final_status.ram {
+        assert_eq!(
+          interpreted.2[*addr as usize], *expected_val as u8,
+          "memory val @ addr \"{addr:#04X}\" mismatch {context}"
+        );
+      }
+    }
+  }
+}
+
 #[derive(Deserialize)]
 struct THTestSpecStatus {
   pub pc: u16,
@@ -1,41 +1,141 @@
 use crate::{
-  cpu::{CPU, debugger::Debugger},
-  memory::Memory,
+  consts::Byte,
+  cpu::{
+    CPU,
+    debugger::{Debugger, ProbeEvent},
+  },
+  memory::{Memory, test_device::TestDevice},
 };
 
+/// Returns the events of all probes made by `debugger` during the instruction.
 pub fn execute_next_instruction(
   cpu: &mut CPU,
   memory: &mut dyn Memory,
   mut debugger: Option<&mut Debugger>,
-) {
+) -> Vec<ProbeEvent> {
+  let mut events = Vec::new();
   loop {
     cpu.tick(memory);
 
     if let Some(dbg) = debugger.as_mut() {
-      dbg.probe(cpu, memory);
+      events.extend(dbg.probe(cpu, memory).events);
     }
 
     if cpu.current_instruction.is_none() {
       break;
     }
   }
+
+  events
 }
 
+/// Runs until a BRK instruction executes or a breakpoint or watchpoint of `debugger` fires.
 pub fn execute_until_break(
   cpu: &mut CPU,
   memory: &mut dyn Memory,
   debugger: &mut Debugger,
 ) -> usize {
+  if !debugger.check_breakpoints(cpu).is_empty() {
+    return cpu.cycle;
+  }
+
   while !cpu.processor_status.get_break_flag() {
-    execute_next_instruction(cpu, memory, Some(debugger));
+    let events = execute_next_instruction(cpu, memory, Some(debugger));
+    if events.iter().any(|event| {
+      matches!(
+        event,
+        ProbeEvent::BreakpointHit { .. } | ProbeEvent::WatchpointHit(_)
+      )
+    }) {
+      break;
+    }
+
     let Some(inst) = debugger.get_last_instruction() else {
       continue;
     };
 
-    if inst.opcode == 0x00 {
+    // interrupts run with a forced BRK opcode too
+    if inst.opcode == 0x00 && inst.name == "BRK" {
       break;
     }
   }
 
   cpu.cycle
 }
+
+/// Runs until the program writes an exit code to `device`, and returns it. Returns `None`
+/// when no exit code was written within `cycle_limit` cycles, e.g. when the program hangs.
+pub fn execute_until_exit(
+  cpu: &mut CPU,
+  memory: &mut dyn Memory,
+  mut debugger: Option<&mut Debugger>,
+  device: &TestDevice,
+  cycle_limit: usize,
+) -> Option<Byte> {
+  let start = cpu.cycle;
+  loop {
+    if let Some(exit_code) = device.exit_code() {
+      return Some(exit_code);
+    }
+    if cpu.cycle - start >= cycle_limit {
+      return None;
+    }
+    execute_next_instruction(cpu, memory, debugger.as_deref_mut());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod execute_until_break {
+    use crate::{
+      cpu::{
+        CPU,
+        debugger::Debugger,
+        instructions::{BRK, INX_IM, JMP_A},
+        tests::MemoryMock,
+        utils::execute_until_break,
+      },
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_stop_at_reset_target_before_executing_it() {
+      let mut memory = Generic64kMem::new();
+      // INX, INX, BRK
+      memory.insert(0x0600, &[INX_IM, INX_IM, BRK]);
+      memory.insert(0xFFFC, &[0x00, 0x06]);
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&memory);
+      let mut debugger = Debugger::new();
+      debugger.add_breakpoint(0x0600..=0x0600);
+      debugger.add_breakpoint(0x0601..=0x0601);
+
+      execute_until_break(&mut cpu, &mut memory, &mut debugger);
+
+      assert_eq!(cpu.program_counter, 0x0600);
+      assert_eq!(cpu.index_register_x, 0);
+
+      execute_until_break(&mut cpu, &mut memory, &mut debugger);
+
+      assert_eq!(cpu.program_counter, 0x0601);
+      assert_eq!(cpu.index_register_x, 1);
+    }
+
+    #[test]
+    fn should_stop_before_instruction_with_breakpoint() {
+      // loop: INX, JMP $0000
+      let mut memory = MemoryMock::new(&[INX_IM, JMP_A, 0x00, 0x00, BRK]);
+      let mut cpu = CPU::new_nmos();
+      cpu.program_counter = 0x00;
+      let mut debugger = Debugger::new();
+      let id = debugger.add_breakpoint(0x01..=0x01);
+      debugger.breakpoint_mut(id).unwrap().ignore_count = 2;
+
+      execute_until_break(&mut cpu, &mut memory, &mut debugger);
+
+      assert_eq!(cpu.program_counter, 0x01);
+      assert_eq!(cpu.index_register_x, 3);
+    }
+  }
+}
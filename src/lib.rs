@@ -1,3 +1,6 @@
 pub mod consts;
 pub mod cpu;
+pub mod loaders;
 pub mod memory;
+pub mod power_on;
+pub mod system;
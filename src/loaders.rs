@@ -0,0 +1,168 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::consts::Word;
+
+pub mod elf;
+pub mod ihex;
+pub mod o65;
+pub mod prg;
+pub mod raw;
+pub mod srec;
+
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+  UnexpectedEnd,
+  MissingStartCode {
+    line: usize,
+  },
+  InvalidHexDigit {
+    line: usize,
+    column: usize,
+  },
+  InvalidLength {
+    line: usize,
+  },
+  ChecksumMismatch {
+    line: usize,
+    expected: u8,
+    actual: u8,
+  },
+  UnsupportedRecord {
+    line: usize,
+    record_type: u8,
+  },
+  AddressOutOfRange {
+    line: usize,
+    addr: u32,
+  },
+  MissingEndRecord,
+  InvalidMagic,
+  UnsupportedFormat(&'static str),
+  UnresolvedSymbol(String),
+}
+
+impl Display for LoadError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      LoadError::UnexpectedEnd => write!(f, "unexpected end of input"),
+      LoadError::MissingStartCode { line } => {
+        write!(f, "line {line}: record does not start with a start code")
+      }
+      LoadError::InvalidHexDigit { line, column } => {
+        write!(f, "line {line}, column {column}: invalid hex digit")
+      }
+      LoadError::InvalidLength { line } => {
+        write!(
+          f,
+          "line {line}: record length does not match its byte count"
+        )
+      }
+      LoadError::ChecksumMismatch {
+        line,
+        expected,
+        actual,
+      } => write!(
+        f,
+        "line {line}: checksum mismatch, expected {expected:#04X}, found {actual:#04X}"
+      ),
+      LoadError::UnsupportedRecord { line, record_type } => {
+        write!(f, "line {line}: unsupported record type {record_type}")
+      }
+      LoadError::AddressOutOfRange { line, addr } => {
+        write!(
+          f,
+          "line {line}: address {addr:#X} is outside of 64KiB address space"
+        )
+      }
+      LoadError::MissingEndRecord => write!(f, "input does not end with an end record"),
+      LoadError::InvalidMagic => write!(f, "input does not start with the expected magic number"),
+      LoadError::UnsupportedFormat(reason) => write!(f, "unsupported format: {reason}"),
+      LoadError::UnresolvedSymbol(name) => write!(f, "symbol \"{name}\" could not be resolved"),
+    }
+  }
+}
+
+impl std::error::Error for LoadError {}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct LoadInfo {
+  pub entry: Option<Word>,
+  pub ranges: Vec<RangeInclusive<Word>>,
+}
+
+impl LoadInfo {
+  fn add_range(&mut self, start: Word, len: usize) {
+    if len == 0 {
+      return;
+    }
+
+    let end = start + (len - 1) as Word;
+    if let Some(last) = self.ranges.last_mut()
+      && last.end().checked_add(1) == Some(start)
+    {
+      *last = *last.start()..=end;
+      return;
+    }
+
+    self.ranges.push(start..=end);
+  }
+}
+
+// Validates that `len` bytes starting at `addr` fit in the address space. `line` is
+// reported as 0 for binary formats.
+fn check_range(line: usize, addr: u32, len: usize) -> Result<Word, LoadError> {
+  let last = addr as usize + len.saturating_sub(1);
+  if last > Word::MAX as usize {
+    return Err(LoadError::AddressOutOfRange {
+      line,
+      addr: last as u32,
+    });
+  }
+
+  Ok(addr as Word)
+}
+
+// Decodes text records of hex digit pairs, shared by Intel HEX and S-records.
+fn decode_hex(line: usize, first_column: usize, text: &str) -> Result<Vec<u8>, LoadError> {
+  let digits = text.as_bytes();
+  if !digits.len().is_multiple_of(2) {
+    return Err(LoadError::InvalidLength { line });
+  }
+
+  digits
+    .chunks(2)
+    .enumerate()
+    .map(|(idx, pair)| {
+      let column = first_column + idx * 2;
+      let hi = hex_digit(pair[0]).ok_or(LoadError::InvalidHexDigit { line, column })?;
+      let lo = hex_digit(pair[1]).ok_or(LoadError::InvalidHexDigit {
+        line,
+        column: column + 1,
+      })?;
+      Ok((hi << 4) | lo)
+    })
+    .collect()
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+  (digit as char).to_digit(16).map(|val| val as u8)
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod load_info {
+    use crate::loaders::LoadInfo;
+
+    #[test]
+    fn should_merge_adjacent_ranges() {
+      let mut uut = LoadInfo::default();
+
+      uut.add_range(0x0200, 0x10);
+      uut.add_range(0x0210, 0x10);
+      uut.add_range(0x0300, 0x01);
+
+      assert_eq!(uut.ranges, vec![0x0200..=0x021F, 0x0300..=0x0300]);
+    }
+  }
+}
@@ -0,0 +1,309 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::{Byte, RESET_VECTOR, Word},
+  cpu::debugger::symbol_table::SymbolTable,
+  loaders::{LoadError, LoadInfo, check_range},
+  memory::Memory,
+};
+
+const MAGIC: [Byte; 4] = [0x7F, b'E', b'L', b'F'];
+const CLASS_32: Byte = 1;
+const LITTLE_ENDIAN: Byte = 1;
+const MACHINE_MOS: u16 = 6502;
+
+const PT_LOAD: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHN_UNDEF: u16 = 0;
+const STT_SECTION: Byte = 3;
+const STT_FILE: Byte = 4;
+
+const PROGRAM_HEADER_SIZE: usize = 32;
+const SECTION_HEADER_SIZE: usize = 40;
+const SYMBOL_SIZE: usize = 16;
+
+#[derive(Debug, PartialEq)]
+pub struct ElfImage {
+  pub entry: Word,
+  pub ranges: Vec<RangeInclusive<Word>>,
+  pub symbols: SymbolTable,
+}
+
+impl ElfImage {
+  /// Points the reset vector at the entry, so `CPU::reset` starts the program.
+  pub fn write_reset_vector(&self, memory: &mut dyn Memory) {
+    let [lo, hi] = self.entry.to_le_bytes();
+    memory.poke(RESET_VECTOR, lo);
+    memory.poke(RESET_VECTOR + 1, hi);
+  }
+}
+
+pub fn load(memory: &mut dyn Memory, elf: &[Byte]) -> Result<ElfImage, LoadError> {
+  if elf.get(0..4) != Some(&MAGIC) {
+    return Err(LoadError::InvalidMagic);
+  }
+  if read_u8(elf, 4)? != CLASS_32 {
+    return Err(LoadError::UnsupportedFormat(
+      "only 32-bit ELF files are supported",
+    ));
+  }
+  if read_u8(elf, 5)? != LITTLE_ENDIAN {
+    return Err(LoadError::UnsupportedFormat(
+      "only little-endian ELF files are supported",
+    ));
+  }
+  if read_u16(elf, 18)? != MACHINE_MOS {
+    return Err(LoadError::UnsupportedFormat("ELF machine is not MOS 6502"));
+  }
+
+  let entry = read_u32(elf, 24)?;
+  let program_headers = read_u32(elf, 28)? as usize;
+  let section_headers = read_u32(elf, 32)? as usize;
+  let program_headers_count = read_u16(elf, 44)? as usize;
+  let section_headers_count = read_u16(elf, 48)? as usize;
+
+  let mut info = LoadInfo::default();
+  for idx in 0..program_headers_count {
+    let header = program_headers + idx * PROGRAM_HEADER_SIZE;
+    if read_u32(elf, header)? != PT_LOAD {
+      continue;
+    }
+
+    let offset = read_u32(elf, header + 4)? as usize;
+    let addr = read_u32(elf, header + 8)?;
+    let file_size = read_u32(elf, header + 16)? as usize;
+    let memory_size = read_u32(elf, header + 20)? as usize;
+    let payload = elf
+      .get(offset..offset + file_size)
+      .ok_or(LoadError::UnexpectedEnd)?;
+
+    let start = check_range(0, addr, memory_size.max(file_size))?;
+    for idx in 0..memory_size.max(file_size) {
+      // bytes past the file image (.bss) are zero-filled
+      memory.poke(
+        start + idx as Word,
+        payload.get(idx).copied().unwrap_or_default(),
+      );
+    }
+    info.add_range(start, memory_size.max(file_size));
+  }
+
+  let mut symbols = SymbolTable::new();
+  for idx in 0..section_headers_count {
+    let header = section_headers + idx * SECTION_HEADER_SIZE;
+    if read_u32(elf, header + 4)? != SHT_SYMTAB {
+      continue;
+    }
+
+    let offset = read_u32(elf, header + 16)? as usize;
+    let size = read_u32(elf, header + 20)? as usize;
+    let strings_header =
+      section_headers + read_u32(elf, header + 24)? as usize * SECTION_HEADER_SIZE;
+    let strings = read_u32(elf, strings_header + 16)? as usize;
+
+    for symbol in (offset..offset + size).step_by(SYMBOL_SIZE) {
+      let symbol_type = read_u8(elf, symbol + 12)? & 0x0F;
+      let section = read_u16(elf, symbol + 14)?;
+      if section == SHN_UNDEF || symbol_type == STT_SECTION || symbol_type == STT_FILE {
+        continue;
+      }
+
+      let name = read_str(elf, strings + read_u32(elf, symbol)? as usize)?;
+      let value = read_u32(elf, symbol + 4)?;
+      if name.is_empty() || value > Word::MAX as u32 {
+        continue;
+      }
+
+      symbols.insert(value as Word, name);
+    }
+  }
+
+  Ok(ElfImage {
+    entry: check_range(0, entry, 1)?,
+    ranges: info.ranges,
+    symbols,
+  })
+}
+
+fn read_u8(elf: &[Byte], offset: usize) -> Result<Byte, LoadError> {
+  elf.get(offset).copied().ok_or(LoadError::UnexpectedEnd)
+}
+
+fn read_u16(elf: &[Byte], offset: usize) -> Result<u16, LoadError> {
+  match elf.get(offset..offset + 2) {
+    Some(&[lo, hi]) => Ok(u16::from_le_bytes([lo, hi])),
+    _ => Err(LoadError::UnexpectedEnd),
+  }
+}
+
+fn read_u32(elf: &[Byte], offset: usize) -> Result<u32, LoadError> {
+  match elf.get(offset..offset + 4) {
+    Some(&[b0, b1, b2, b3]) => Ok(u32::from_le_bytes([b0, b1, b2, b3])),
+    _ => Err(LoadError::UnexpectedEnd),
+  }
+}
+
+fn read_str(elf: &[Byte], offset: usize) -> Result<&str, LoadError> {
+  let tail = elf.get(offset..).ok_or(LoadError::UnexpectedEnd)?;
+  let len = tail
+    .iter()
+    .position(|byte| *byte == 0)
+    .ok_or(LoadError::UnexpectedEnd)?;
+
+  std::str::from_utf8(&tail[..len])
+    .map_err(|_| LoadError::UnsupportedFormat("symbol name is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+  // Assembles an ELF with a single PT_LOAD segment and a symbol table.
+  fn build_elf(machine: u16, entry: u32, addr: u32, payload: &[u8], mem_size: u32) -> Vec<u8> {
+    let strings = b"\0main\0counter\0";
+    let symbols: [(u32, u32, u8, u16); 3] = [
+      (1, 0x0600, 0x12, 1), // main: global function
+      (6, 0x0300, 0x11, 2), // counter: global object
+      (0, 0x0000, 0x03, 1), // section symbol, skipped
+    ];
+
+    let program_header_offset = 52;
+    let payload_offset = program_header_offset + 32;
+    let symtab_offset = payload_offset + payload.len();
+    let strtab_offset = symtab_offset + (symbols.len() + 1) * 16;
+    let section_header_offset = strtab_offset + strings.len();
+
+    let mut elf = vec![0x7F, b'E', b'L', b'F', 1, 1, 1];
+    elf.resize(16, 0);
+    elf.extend(2u16.to_le_bytes()); // executable
+    elf.extend(machine.to_le_bytes());
+    elf.extend(1u32.to_le_bytes());
+    elf.extend(entry.to_le_bytes());
+    elf.extend((program_header_offset as u32).to_le_bytes());
+    elf.extend((section_header_offset as u32).to_le_bytes());
+    elf.extend(0u32.to_le_bytes());
+    elf.extend(52u16.to_le_bytes());
+    elf.extend(32u16.to_le_bytes());
+    elf.extend(1u16.to_le_bytes());
+    elf.extend(40u16.to_le_bytes());
+    elf.extend(3u16.to_le_bytes());
+    elf.extend(0u16.to_le_bytes());
+
+    for field in [
+      1,
+      payload_offset as u32,
+      addr,
+      addr,
+      payload.len() as u32,
+      mem_size,
+      5,
+      1,
+    ] {
+      elf.extend(field.to_le_bytes());
+    }
+    elf.extend(payload);
+
+    elf.extend([0; 16]);
+    for (name, value, info, section) in symbols {
+      elf.extend(name.to_le_bytes());
+      elf.extend(value.to_le_bytes());
+      elf.extend(0u32.to_le_bytes());
+      elf.push(info);
+      elf.push(0);
+      elf.extend(section.to_le_bytes());
+    }
+    elf.extend(strings);
+
+    let symtab_size = ((symbols.len() + 1) * 16) as u32;
+    let sections: [(u32, u32, u32, u32, u32); 3] = [
+      (0, 0, 0, 0, 0),
+      (2, symtab_offset as u32, symtab_size, 2, 16), // .symtab linked to .strtab
+      (3, strtab_offset as u32, strings.len() as u32, 0, 0),
+    ];
+    for (kind, offset, size, link, entry_size) in sections {
+      for field in [0, kind, 0, 0, offset, size, link, 0, 1, entry_size] {
+        elf.extend(field.to_le_bytes());
+      }
+    }
+
+    elf
+  }
+
+  #[cfg(test)]
+  mod load {
+    use super::build_elf;
+    use crate::{
+      cpu::debugger::Symbols,
+      loaders::{LoadError, elf::load},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_place_load_segments_and_zero_fill_rest_of_memory_size() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0603, &[0xFF]);
+      let elf = build_elf(6502, 0x0600, 0x0600, &[0xA9, 0x01, 0x60], 4);
+
+      let image = load(&mut memory, &elf).unwrap();
+
+      assert_eq!(&memory[0x0600..0x0604], &[0xA9, 0x01, 0x60, 0x00]);
+      assert_eq!(image.entry, 0x0600);
+      assert_eq!(image.ranges, vec![0x0600..=0x0603]);
+    }
+
+    #[test]
+    fn should_return_symbols_from_symtab() {
+      let mut memory = Generic64kMem::new();
+      let elf = build_elf(6502, 0x0600, 0x0600, &[0x60], 1);
+
+      let image = load(&mut memory, &elf).unwrap();
+
+      assert_eq!(image.symbols.get(&0x0600), Some(String::from("main")));
+      assert_eq!(image.symbols.get(&0x0300), Some(String::from("counter")));
+      assert_eq!(image.symbols.len(), 2);
+    }
+
+    #[test]
+    fn should_write_entry_into_reset_vector() {
+      let mut memory = Generic64kMem::new();
+      let elf = build_elf(6502, 0x0634, 0x0600, &[0x60], 1);
+
+      let image = load(&mut memory, &elf).unwrap();
+      image.write_reset_vector(&mut memory);
+
+      assert_eq!(&memory[0xFFFC..0xFFFE], &[0x34, 0x06]);
+    }
+
+    #[test]
+    fn should_reject_other_machines() {
+      let mut memory = Generic64kMem::new();
+      let elf = build_elf(0x28, 0x0600, 0x0600, &[0x60], 1);
+
+      assert_eq!(
+        load(&mut memory, &elf),
+        Err(LoadError::UnsupportedFormat("ELF machine is not MOS 6502"))
+      );
+    }
+
+    #[test]
+    fn should_reject_segments_outside_of_address_space() {
+      let mut memory = Generic64kMem::new();
+      let elf = build_elf(6502, 0x0600, 0xFFFF, &[0xEA, 0xEA], 2);
+
+      assert_eq!(
+        load(&mut memory, &elf),
+        Err(LoadError::AddressOutOfRange {
+          line: 0,
+          addr: 0x10000
+        })
+      );
+    }
+
+    #[test]
+    fn should_reject_truncated_input() {
+      let mut memory = Generic64kMem::new();
+      let elf = build_elf(6502, 0x0600, 0x0600, &[0x60], 1);
+
+      assert_eq!(load(&mut memory, &elf[..40]), Err(LoadError::UnexpectedEnd));
+      assert_eq!(load(&mut memory, b"MZ"), Err(LoadError::InvalidMagic));
+    }
+  }
+}
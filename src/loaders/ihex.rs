@@ -0,0 +1,261 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::{Byte, Word},
+  loaders::{LoadError, LoadInfo, check_range, decode_hex},
+  memory::Memory,
+};
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+const BYTES_PER_RECORD: usize = 16;
+
+pub fn load(memory: &mut dyn Memory, text: &str) -> Result<LoadInfo, LoadError> {
+  let mut info = LoadInfo::default();
+  let mut base: u32 = 0;
+
+  for (idx, record) in text.lines().enumerate() {
+    let line = idx + 1;
+    let record = record.trim();
+    if record.is_empty() {
+      continue;
+    }
+
+    let Some(digits) = record.strip_prefix(':') else {
+      return Err(LoadError::MissingStartCode { line });
+    };
+
+    let bytes = decode_hex(line, 2, digits)?;
+    if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+      return Err(LoadError::InvalidLength { line });
+    }
+
+    let (content, [checksum]) = bytes.split_at(bytes.len() - 1) else {
+      unreachable!("split leaves exactly one checksum byte");
+    };
+    let expected = content
+      .iter()
+      .fold(0u8, |acc, val| acc.wrapping_add(*val))
+      .wrapping_neg();
+    if expected != *checksum {
+      return Err(LoadError::ChecksumMismatch {
+        line,
+        expected,
+        actual: *checksum,
+      });
+    }
+
+    let offset = u16::from_be_bytes([content[1], content[2]]) as u32;
+    let record_type = content[3];
+    let data = &content[4..];
+    match record_type {
+      DATA => {
+        let addr = check_range(line, base + offset, data.len())?;
+        for (idx, value) in data.iter().enumerate() {
+          memory.poke(addr + idx as Word, *value);
+        }
+        info.add_range(addr, data.len());
+      }
+      END_OF_FILE => return Ok(info),
+      EXTENDED_SEGMENT_ADDRESS if data.len() == 2 => {
+        base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 4;
+      }
+      EXTENDED_LINEAR_ADDRESS if data.len() == 2 => {
+        base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 16;
+      }
+      START_SEGMENT_ADDRESS if data.len() == 4 => {
+        let segment = u16::from_be_bytes([data[0], data[1]]) as u32;
+        let pointer = u16::from_be_bytes([data[2], data[3]]) as u32;
+        info.entry = Some(check_range(line, (segment << 4) + pointer, 1)?);
+      }
+      START_LINEAR_ADDRESS if data.len() == 4 => {
+        let addr = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        info.entry = Some(check_range(line, addr, 1)?);
+      }
+      EXTENDED_SEGMENT_ADDRESS
+      | EXTENDED_LINEAR_ADDRESS
+      | START_SEGMENT_ADDRESS
+      | START_LINEAR_ADDRESS => return Err(LoadError::InvalidLength { line }),
+      _ => return Err(LoadError::UnsupportedRecord { line, record_type }),
+    }
+  }
+
+  Err(LoadError::MissingEndRecord)
+}
+
+pub fn write(memory: &dyn Memory, range: RangeInclusive<Word>, entry: Option<Word>) -> String {
+  let mut text = String::new();
+  let start = *range.start() as usize;
+  let end = *range.end() as usize;
+  for chunk_start in (start..=end).step_by(BYTES_PER_RECORD) {
+    let chunk_end = end.min(chunk_start + BYTES_PER_RECORD - 1);
+    let data: Vec<Byte> = (chunk_start..=chunk_end)
+      .map(|addr| memory[addr as Word])
+      .collect();
+    push_record(&mut text, chunk_start as Word, DATA, &data);
+  }
+
+  if let Some(entry) = entry {
+    push_record(
+      &mut text,
+      0,
+      START_LINEAR_ADDRESS,
+      &(entry as u32).to_be_bytes(),
+    );
+  }
+  push_record(&mut text, 0, END_OF_FILE, &[]);
+
+  text
+}
+
+fn push_record(text: &mut String, addr: Word, record_type: u8, data: &[Byte]) {
+  let mut bytes = vec![data.len() as u8];
+  bytes.extend(addr.to_be_bytes());
+  bytes.push(record_type);
+  bytes.extend(data);
+  let checksum = bytes
+    .iter()
+    .fold(0u8, |acc, val| acc.wrapping_add(*val))
+    .wrapping_neg();
+  bytes.push(checksum);
+
+  text.push(':');
+  for byte in bytes {
+    text.push_str(&format!("{byte:02X}"));
+  }
+  text.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod load {
+    use crate::{
+      loaders::{LoadError, ihex::load},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_load_data_records_and_start_address() {
+      let mut memory = Generic64kMem::new();
+      let text = ":03060000A9018DC0\n:0400000500000600F1\n:00000001FF\n";
+
+      let info = load(&mut memory, text).unwrap();
+
+      assert_eq!(&memory[0x0600..0x0603], &[0xA9, 0x01, 0x8D]);
+      assert_eq!(info.entry, Some(0x0600));
+      assert_eq!(info.ranges, vec![0x0600..=0x0602]);
+    }
+
+    #[test]
+    fn should_report_checksum_mismatch_with_line() {
+      let mut memory = Generic64kMem::new();
+      let corrupted = ":03060000A9018DC1\n:00000001FF\n";
+
+      assert_eq!(
+        load(&mut memory, corrupted),
+        Err(LoadError::ChecksumMismatch {
+          line: 1,
+          expected: 0xC0,
+          actual: 0xC1
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_address_out_of_range_after_extended_linear_address() {
+      let mut memory = Generic64kMem::new();
+      let text = ":020000040001F9\n:01000000EA15\n:00000001FF\n";
+
+      assert_eq!(
+        load(&mut memory, text),
+        Err(LoadError::AddressOutOfRange {
+          line: 2,
+          addr: 0x10000
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_invalid_hex_digit_with_column() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, ":01000000XA15\n"),
+        Err(LoadError::InvalidHexDigit {
+          line: 1,
+          column: 10
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_missing_start_code() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, "00000001FF\n"),
+        Err(LoadError::MissingStartCode { line: 1 })
+      );
+    }
+
+    #[test]
+    fn should_report_byte_count_mismatch() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, ":02000000EA14\n"),
+        Err(LoadError::InvalidLength { line: 1 })
+      );
+    }
+
+    #[test]
+    fn should_report_missing_end_record() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, ":01000000EA15\n"),
+        Err(LoadError::MissingEndRecord)
+      );
+    }
+  }
+
+  #[cfg(test)]
+  mod write {
+    use crate::{
+      loaders::ihex::{load, write},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_write_data_start_and_end_records() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0600, &[0xA9, 0x01, 0x8D]);
+
+      assert_eq!(
+        write(&memory, 0x0600..=0x0602, Some(0x0600)),
+        ":03060000A9018DC0\n:0400000500000600F1\n:00000001FF\n"
+      );
+    }
+
+    #[test]
+    fn should_split_data_into_records_that_load_back() {
+      let mut source = Generic64kMem::new();
+      let payload: Vec<u8> = (0..40).collect();
+      source.insert(0x1000, &payload);
+
+      let text = write(&source, 0x1000..=0x1027, None);
+      let mut target = Generic64kMem::new();
+      let info = load(&mut target, &text).unwrap();
+
+      assert_eq!(text.lines().count(), 4);
+      assert_eq!(&target[0x1000..0x1028], payload.as_slice());
+      assert_eq!(info.ranges, vec![0x1000..=0x1027]);
+    }
+  }
+}
@@ -0,0 +1,466 @@
+use std::ops::Range;
+
+use crate::{
+  consts::{Byte, Word},
+  cpu::debugger::symbol_table::SymbolTable,
+  loaders::{LoadError, check_range},
+  memory::Memory,
+};
+
+const MAGIC: [Byte; 6] = [0x01, 0x00, b'o', b'6', b'5', 0x00];
+
+const MODE_PAGEWISE_RELOCATION: u16 = 0x4000;
+const MODE_LONG_SIZE: u16 = 0x2000;
+const MODE_BSS_ZERO: u16 = 0x0200;
+
+const SEGMENT_UNDEFINED: Byte = 0;
+const SEGMENT_ABSOLUTE: Byte = 1;
+const SEGMENT_TEXT: Byte = 2;
+const SEGMENT_DATA: Byte = 3;
+const SEGMENT_BSS: Byte = 4;
+const SEGMENT_ZERO: Byte = 5;
+
+const RELOC_WORD: Byte = 0x80;
+const RELOC_HIGH: Byte = 0x40;
+const RELOC_LOW: Byte = 0x20;
+
+/// Load addresses of module segments. Segments without an address follow the previous one,
+/// except for the zero page segment which stays where it was assembled.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Layout {
+  pub text: Word,
+  pub data: Option<Word>,
+  pub bss: Option<Word>,
+  pub zero: Option<Word>,
+}
+
+impl Layout {
+  pub fn at(text: Word) -> Self {
+    Layout {
+      text,
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct O65Module {
+  pub text: Range<Word>,
+  pub data: Range<Word>,
+  pub bss: Range<Word>,
+  pub zero: Range<Word>,
+  pub exports: SymbolTable,
+}
+
+#[derive(Clone, Copy)]
+struct Segment {
+  base: Word,
+  len: Word,
+  load: Word,
+}
+
+impl Segment {
+  fn delta(&self) -> Word {
+    self.load.wrapping_sub(self.base)
+  }
+
+  fn loaded_range(&self) -> Range<Word> {
+    self.load..self.load.wrapping_add(self.len)
+  }
+}
+
+/// Loads a module at the given layout, relocating text and data and resolving undefined
+/// references by name against `imports`.
+pub fn load(
+  memory: &mut dyn Memory,
+  o65: &[Byte],
+  layout: Layout,
+  imports: &SymbolTable,
+) -> Result<O65Module, LoadError> {
+  let mut reader = Reader { o65, pos: 0 };
+  if reader.bytes(MAGIC.len())? != MAGIC {
+    return Err(LoadError::InvalidMagic);
+  }
+
+  let mode = reader.word()?;
+  if mode & MODE_LONG_SIZE > 0 {
+    return Err(LoadError::UnsupportedFormat(
+      "o65 files with 32-bit sizes are not supported",
+    ));
+  }
+
+  let text_base = reader.word()?;
+  let text_len = reader.word()?;
+  let data_base = reader.word()?;
+  let data_len = reader.word()?;
+  let bss_base = reader.word()?;
+  let bss_len = reader.word()?;
+  let zero_base = reader.word()?;
+  let zero_len = reader.word()?;
+  let _stack_size = reader.word()?;
+
+  let text = segment(text_base, text_len, layout.text)?;
+  let data_load = layout.data.unwrap_or(text.load.wrapping_add(text.len));
+  let data = segment(data_base, data_len, data_load)?;
+  let bss_load = layout.bss.unwrap_or(data.load.wrapping_add(data.len));
+  let bss = segment(bss_base, bss_len, bss_load)?;
+  let zero = segment(zero_base, zero_len, layout.zero.unwrap_or(zero_base))?;
+
+  let pagewise = mode & MODE_PAGEWISE_RELOCATION > 0;
+  if pagewise
+    && [text, data, bss, zero]
+      .iter()
+      .any(|seg| seg.delta() & 0xFF != 0)
+  {
+    return Err(LoadError::UnsupportedFormat(
+      "pagewise relocatable module must be moved by whole pages",
+    ));
+  }
+
+  loop {
+    let len = reader.byte()?;
+    if len == 0 {
+      break;
+    }
+    // options (file name, assembler, author...) carry nothing needed for loading
+    reader.bytes(len.saturating_sub(1) as usize)?;
+  }
+
+  let mut text_image = reader.bytes(text.len as usize)?.to_vec();
+  let mut data_image = reader.bytes(data.len as usize)?.to_vec();
+
+  let undefined_count = reader.word()?;
+  let mut undefined = Vec::with_capacity(undefined_count as usize);
+  for _ in 0..undefined_count {
+    let name = reader.string()?;
+    let addr = imports
+      .address_of(name)
+      .ok_or_else(|| LoadError::UnresolvedSymbol(String::from(name)))?;
+    undefined.push(addr);
+  }
+
+  let segments = Segments {
+    text,
+    data,
+    bss,
+    zero,
+    undefined,
+    pagewise,
+  };
+  segments.relocate(&mut reader, &mut text_image, text.base)?;
+  segments.relocate(&mut reader, &mut data_image, data.base)?;
+
+  let mut exports = SymbolTable::new();
+  for _ in 0..reader.word()? {
+    let name = reader.string()?;
+    let segment_id = reader.byte()?;
+    let value = reader.word()?;
+    exports.insert(value.wrapping_add(segments.delta(segment_id)?), name);
+  }
+
+  copy(memory, text.load, &text_image)?;
+  copy(memory, data.load, &data_image)?;
+  if mode & MODE_BSS_ZERO > 0 {
+    copy(memory, bss.load, &vec![0; bss.len as usize])?;
+  }
+
+  Ok(O65Module {
+    text: text.loaded_range(),
+    data: data.loaded_range(),
+    bss: bss.loaded_range(),
+    zero: zero.loaded_range(),
+    exports,
+  })
+}
+
+fn segment(base: Word, len: Word, load: Word) -> Result<Segment, LoadError> {
+  check_range(0, load.into(), len.into())?;
+  Ok(Segment { base, len, load })
+}
+
+fn copy(memory: &mut dyn Memory, addr: Word, payload: &[Byte]) -> Result<(), LoadError> {
+  check_range(0, addr.into(), payload.len())?;
+  for (offset, value) in payload.iter().enumerate() {
+    memory.poke(addr + offset as Word, *value);
+  }
+
+  Ok(())
+}
+
+struct Segments {
+  text: Segment,
+  data: Segment,
+  bss: Segment,
+  zero: Segment,
+  undefined: Vec<Word>,
+  pagewise: bool,
+}
+
+impl Segments {
+  fn delta(&self, segment_id: Byte) -> Result<Word, LoadError> {
+    match segment_id {
+      SEGMENT_ABSOLUTE => Ok(0),
+      SEGMENT_TEXT => Ok(self.text.delta()),
+      SEGMENT_DATA => Ok(self.data.delta()),
+      SEGMENT_BSS => Ok(self.bss.delta()),
+      SEGMENT_ZERO => Ok(self.zero.delta()),
+      _ => Err(LoadError::UnsupportedFormat("unknown o65 segment id")),
+    }
+  }
+
+  // Applies one relocation table to a segment image assembled at `base`.
+  fn relocate(&self, reader: &mut Reader, image: &mut [Byte], base: Word) -> Result<(), LoadError> {
+    // offsets are relative to the previous relocation, starting from `base - 1`
+    let mut next_addr = base as usize;
+    loop {
+      let offset = reader.byte()?;
+      match offset {
+        0 => return Ok(()),
+        255 => {
+          next_addr += 254;
+          continue;
+        }
+        _ => next_addr += offset as usize,
+      }
+      let addr = next_addr - 1;
+
+      let type_byte = reader.byte()?;
+      let segment_id = type_byte & 0x1F;
+      let delta = if segment_id == SEGMENT_UNDEFINED {
+        let idx = reader.word()? as usize;
+        *self.undefined.get(idx).ok_or(LoadError::UnsupportedFormat(
+          "undefined reference index out of range",
+        ))?
+      } else {
+        self.delta(segment_id)?
+      };
+
+      let idx = addr
+        .checked_sub(base as usize)
+        .filter(|idx| *idx < image.len())
+        .ok_or(LoadError::UnsupportedFormat(
+          "relocation outside of segment",
+        ))?;
+      match type_byte & 0xE0 {
+        RELOC_WORD => {
+          let target = image
+            .get_mut(idx..idx + 2)
+            .ok_or(LoadError::UnsupportedFormat(
+              "relocation outside of segment",
+            ))?;
+          let value = Word::from_le_bytes([target[0], target[1]]).wrapping_add(delta);
+          target.copy_from_slice(&value.to_le_bytes());
+        }
+        RELOC_HIGH => {
+          let lo = if self.pagewise { 0 } else { reader.byte()? };
+          let value = Word::from_le_bytes([lo, image[idx]]).wrapping_add(delta);
+          image[idx] = value.to_le_bytes()[1];
+        }
+        RELOC_LOW => {
+          image[idx] = image[idx].wrapping_add(delta.to_le_bytes()[0]);
+        }
+        _ => {
+          return Err(LoadError::UnsupportedFormat(
+            "segment relocations are not supported",
+          ));
+        }
+      }
+    }
+  }
+}
+
+struct Reader<'a> {
+  o65: &'a [Byte],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn bytes(&mut self, len: usize) -> Result<&'a [Byte], LoadError> {
+    let bytes = self
+      .o65
+      .get(self.pos..self.pos + len)
+      .ok_or(LoadError::UnexpectedEnd)?;
+    self.pos += len;
+
+    Ok(bytes)
+  }
+
+  fn byte(&mut self) -> Result<Byte, LoadError> {
+    Ok(self.bytes(1)?[0])
+  }
+
+  fn word(&mut self) -> Result<Word, LoadError> {
+    let bytes = self.bytes(2)?;
+    Ok(Word::from_le_bytes([bytes[0], bytes[1]]))
+  }
+
+  fn string(&mut self) -> Result<&'a str, LoadError> {
+    let tail = self.o65.get(self.pos..).ok_or(LoadError::UnexpectedEnd)?;
+    let len = tail
+      .iter()
+      .position(|byte| *byte == 0)
+      .ok_or(LoadError::UnexpectedEnd)?;
+    self.pos += len + 1;
+
+    std::str::from_utf8(&tail[..len])
+      .map_err(|_| LoadError::UnsupportedFormat("symbol name is not valid UTF-8"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  // Module assembled at $1000 with data at $2000:
+  //   $1000 JSR putc      ; undefined reference
+  //   $1003 LDA #>message ; data high byte, low byte $05
+  //   $1005 JMP $1000     ; text word
+  //   $2000 .word $1003   ; text word in data
+  //   $2002 "hello"
+  fn build_o65(mode: u16) -> Vec<u8> {
+    let mut o65 = vec![0x01, 0x00, b'o', b'6', b'5', 0x00];
+    o65.extend(mode.to_le_bytes());
+    for field in [0x1000u16, 8, 0x2000, 7, 0x3000, 4, 0x0080, 0, 0] {
+      o65.extend(field.to_le_bytes());
+    }
+    o65.extend([0x05, 0x00, b'a', b'b', 0x00]); // file name option
+    o65.push(0x00);
+
+    o65.extend([0x20, 0x00, 0x00, 0xA9, 0x20, 0x4C, 0x00, 0x10]);
+    o65.extend([0x03, 0x10, b'h', b'e', b'l', b'l', b'o']);
+
+    o65.extend(1u16.to_le_bytes());
+    o65.extend(b"putc\0");
+
+    // text relocations
+    o65.extend([0x02, 0x80, 0x00, 0x00]); // $1001 word, undefined #0
+    o65.extend([0x03, 0x43, 0x05]); // $1004 high byte of data, low $05
+    o65.extend([0x02, 0x82]); // $1006 word of text
+    o65.push(0x00);
+    // data relocations
+    o65.extend([0x01, 0x82]); // $2000 word of text
+    o65.push(0x00);
+
+    o65.extend(2u16.to_le_bytes());
+    o65.extend(b"start\0");
+    o65.push(0x02);
+    o65.extend(0x1000u16.to_le_bytes());
+    o65.extend(b"message\0");
+    o65.push(0x03);
+    o65.extend(0x2002u16.to_le_bytes());
+
+    o65
+  }
+
+  #[cfg(test)]
+  mod load {
+    use super::build_o65;
+    use crate::{
+      cpu::debugger::{Symbols, symbol_table::SymbolTable},
+      loaders::{
+        LoadError,
+        o65::{Layout, load},
+      },
+      memory::Generic64kMem,
+    };
+
+    fn imports() -> SymbolTable {
+      let mut imports = SymbolTable::new();
+      imports.insert(0xFFD2, "putc");
+      imports
+    }
+
+    #[test]
+    fn should_load_module_unchanged_at_assembled_addresses() {
+      let mut memory = Generic64kMem::new();
+      let layout = Layout {
+        text: 0x1000,
+        data: Some(0x2000),
+        ..Default::default()
+      };
+
+      let module = load(&mut memory, &build_o65(0), layout, &imports()).unwrap();
+
+      assert_eq!(
+        &memory[0x1000..0x1008],
+        &[0x20, 0xD2, 0xFF, 0xA9, 0x20, 0x4C, 0x00, 0x10]
+      );
+      assert_eq!(&memory[0x2000..0x2002], &[0x03, 0x10]);
+      assert_eq!(module.text, 0x1000..0x1008);
+      assert_eq!(module.data, 0x2000..0x2007);
+    }
+
+    #[test]
+    fn should_relocate_text_and_data_to_another_base() {
+      let mut memory = Generic64kMem::new();
+
+      let module = load(&mut memory, &build_o65(0), Layout::at(0x4000), &imports()).unwrap();
+
+      // data follows text at $4008, so message moves to $400A
+      assert_eq!(
+        &memory[0x4000..0x4008],
+        &[0x20, 0xD2, 0xFF, 0xA9, 0x40, 0x4C, 0x00, 0x40]
+      );
+      assert_eq!(
+        &memory[0x4008..0x400F],
+        &[0x03, 0x40, b'h', b'e', b'l', b'l', b'o']
+      );
+      assert_eq!(module.data, 0x4008..0x400F);
+      assert_eq!(module.bss, 0x400F..0x4013);
+    }
+
+    #[test]
+    fn should_expose_relocated_exports_as_symbols() {
+      let mut memory = Generic64kMem::new();
+
+      let module = load(&mut memory, &build_o65(0), Layout::at(0x4000), &imports()).unwrap();
+
+      assert_eq!(module.exports.get(&0x4000), Some(String::from("start")));
+      assert_eq!(module.exports.address_of("message"), Some(0x400A));
+    }
+
+    #[test]
+    fn should_zero_bss_when_module_requests_it() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x400F, &[0xFF; 4]);
+
+      load(
+        &mut memory,
+        &build_o65(0x0200),
+        Layout::at(0x4000),
+        &imports(),
+      )
+      .unwrap();
+
+      assert_eq!(&memory[0x400F..0x4013], &[0x00; 4]);
+    }
+
+    #[test]
+    fn should_fail_when_import_is_missing() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(
+          &mut memory,
+          &build_o65(0),
+          Layout::at(0x4000),
+          &SymbolTable::new()
+        ),
+        Err(LoadError::UnresolvedSymbol(String::from("putc")))
+      );
+    }
+
+    #[test]
+    fn should_fail_on_invalid_magic_and_truncated_input() {
+      let mut memory = Generic64kMem::new();
+      let o65 = build_o65(0);
+
+      assert_eq!(
+        load(&mut memory, &o65[1..], Layout::at(0x4000), &imports()),
+        Err(LoadError::InvalidMagic)
+      );
+      assert_eq!(
+        load(&mut memory, &o65[..40], Layout::at(0x4000), &imports()),
+        Err(LoadError::UnexpectedEnd)
+      );
+    }
+  }
+}
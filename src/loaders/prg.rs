@@ -0,0 +1,72 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::{Byte, Word},
+  loaders::{LoadError, LoadInfo, raw},
+  memory::Memory,
+};
+
+// C64 program file: 2-byte little-endian load address followed by the payload.
+pub fn load(memory: &mut dyn Memory, prg: &[Byte]) -> Result<LoadInfo, LoadError> {
+  let [lo, hi, payload @ ..] = prg else {
+    return Err(LoadError::UnexpectedEnd);
+  };
+
+  let addr = Word::from_le_bytes([*lo, *hi]);
+  let mut info = raw::load(memory, addr, payload)?;
+  info.entry = Some(addr);
+
+  Ok(info)
+}
+
+pub fn write(memory: &dyn Memory, range: RangeInclusive<Word>) -> Vec<Byte> {
+  let mut prg = range.start().to_le_bytes().to_vec();
+  prg.extend(raw::write(memory, range));
+
+  prg
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod load {
+    use crate::{
+      loaders::{LoadError, prg::load},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_load_payload_at_address_from_header() {
+      let mut memory = Generic64kMem::new();
+
+      let info = load(&mut memory, &[0x01, 0x08, 0x0B, 0x08]).unwrap();
+
+      assert_eq!(&memory[0x0801..0x0803], &[0x0B, 0x08]);
+      assert_eq!(info.entry, Some(0x0801));
+      assert_eq!(info.ranges, vec![0x0801..=0x0802]);
+    }
+
+    #[test]
+    fn should_fail_when_header_is_incomplete() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(load(&mut memory, &[0x01]), Err(LoadError::UnexpectedEnd));
+    }
+  }
+
+  #[cfg(test)]
+  mod write {
+    use crate::{loaders::prg::write, memory::Generic64kMem};
+
+    #[test]
+    fn should_prefix_payload_with_load_address() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0801, &[0x0B, 0x08]);
+
+      assert_eq!(
+        write(&memory, 0x0801..=0x0802),
+        vec![0x01, 0x08, 0x0B, 0x08]
+      );
+    }
+  }
+}
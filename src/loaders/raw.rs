@@ -0,0 +1,86 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::{Byte, Word},
+  loaders::{LoadError, LoadInfo, check_range},
+  memory::Memory,
+};
+
+pub fn load(memory: &mut dyn Memory, addr: Word, payload: &[Byte]) -> Result<LoadInfo, LoadError> {
+  check_range(0, addr.into(), payload.len())?;
+
+  for (offset, value) in payload.iter().enumerate() {
+    memory.poke(addr + offset as Word, *value);
+  }
+
+  let mut info = LoadInfo::default();
+  info.add_range(addr, payload.len());
+  Ok(info)
+}
+
+pub fn write(memory: &dyn Memory, range: RangeInclusive<Word>) -> Vec<Byte> {
+  range.map(|addr| memory[addr]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod load {
+    use crate::{
+      loaders::{LoadError, raw::load},
+      memory::{Generic64kMem, Memory, WriteProtection, map::MemoryMap},
+    };
+
+    #[test]
+    fn should_copy_payload_at_address() {
+      let mut memory = Generic64kMem::new();
+
+      let info = load(&mut memory, 0x0600, &[0xA9, 0x01]).unwrap();
+
+      assert_eq!(&memory[0x0600..0x0602], &[0xA9, 0x01]);
+      assert_eq!(info.ranges, vec![0x0600..=0x0601]);
+    }
+
+    #[test]
+    fn should_load_into_protected_range_without_faults() {
+      let mut rom = Generic64kMem::new();
+      rom.protect(0x0000..=0x1FFF, WriteProtection::Fault);
+      let mut memory = MemoryMap::new();
+      memory.map(0xE000..=0xFFFF, rom);
+
+      load(&mut memory, 0xE000, &[0x4C, 0x00, 0xE0]).unwrap();
+
+      assert_eq!(memory.peek(0xE000), 0x4C);
+      assert_eq!(memory.peek(0xE002), 0xE0);
+      assert!(memory.take_faults().is_empty());
+    }
+
+    #[test]
+    fn should_fail_when_payload_does_not_fit_in_address_space() {
+      let mut memory = Generic64kMem::new();
+
+      let result = load(&mut memory, 0xFFFF, &[0xA9, 0x01]);
+
+      assert_eq!(
+        result,
+        Err(LoadError::AddressOutOfRange {
+          line: 0,
+          addr: 0x10000
+        })
+      );
+    }
+  }
+
+  #[cfg(test)]
+  mod write {
+    use crate::{loaders::raw::write, memory::Generic64kMem};
+
+    #[test]
+    fn should_dump_range_of_memory() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x10, &[0x01, 0x02, 0x03]);
+
+      assert_eq!(write(&memory, 0x10..=0x12), vec![0x01, 0x02, 0x03]);
+    }
+  }
+}
@@ -0,0 +1,235 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+  consts::{Byte, Word},
+  loaders::{LoadError, LoadInfo, check_range, decode_hex},
+  memory::Memory,
+};
+
+const BYTES_PER_RECORD: usize = 16;
+
+pub fn load(memory: &mut dyn Memory, text: &str) -> Result<LoadInfo, LoadError> {
+  let mut info = LoadInfo::default();
+
+  for (idx, record) in text.lines().enumerate() {
+    let line = idx + 1;
+    let record = record.trim();
+    if record.is_empty() {
+      continue;
+    }
+
+    let mut chars = record.chars();
+    if chars.next() != Some('S') {
+      return Err(LoadError::MissingStartCode { line });
+    }
+    let record_type = chars
+      .next()
+      .and_then(|digit| digit.to_digit(10))
+      .ok_or(LoadError::InvalidHexDigit { line, column: 2 })? as u8;
+
+    let bytes = decode_hex(line, 3, chars.as_str())?;
+    if bytes.len() < 2 || bytes.len() != bytes[0] as usize + 1 {
+      return Err(LoadError::InvalidLength { line });
+    }
+
+    let (content, [checksum]) = bytes.split_at(bytes.len() - 1) else {
+      unreachable!("split leaves exactly one checksum byte");
+    };
+    let expected = !content.iter().fold(0u8, |acc, val| acc.wrapping_add(*val));
+    if expected != *checksum {
+      return Err(LoadError::ChecksumMismatch {
+        line,
+        expected,
+        actual: *checksum,
+      });
+    }
+
+    let address_len = match record_type {
+      0 | 1 | 5 | 9 => 2,
+      2 | 6 | 8 => 3,
+      3 | 7 => 4,
+      _ => return Err(LoadError::UnsupportedRecord { line, record_type }),
+    };
+    let fields = &content[1..];
+    if fields.len() < address_len {
+      return Err(LoadError::InvalidLength { line });
+    }
+
+    let (addr_bytes, data) = fields.split_at(address_len);
+    let addr = addr_bytes
+      .iter()
+      .fold(0u32, |acc, val| (acc << 8) | *val as u32);
+    match record_type {
+      1..=3 => {
+        let addr = check_range(line, addr, data.len())?;
+        for (idx, value) in data.iter().enumerate() {
+          memory.poke(addr + idx as Word, *value);
+        }
+        info.add_range(addr, data.len());
+      }
+      7..=9 => {
+        info.entry = Some(check_range(line, addr, 1)?);
+        return Ok(info);
+      }
+      // header and record count records carry no memory contents
+      _ => {}
+    }
+  }
+
+  Err(LoadError::MissingEndRecord)
+}
+
+pub fn write(memory: &dyn Memory, range: RangeInclusive<Word>, entry: Option<Word>) -> String {
+  let mut text = String::new();
+  push_record(&mut text, 0, 0, &[]);
+
+  let start = *range.start() as usize;
+  let end = *range.end() as usize;
+  let mut records: u16 = 0;
+  for chunk_start in (start..=end).step_by(BYTES_PER_RECORD) {
+    let chunk_end = end.min(chunk_start + BYTES_PER_RECORD - 1);
+    let data: Vec<Byte> = (chunk_start..=chunk_end)
+      .map(|addr| memory[addr as Word])
+      .collect();
+    push_record(&mut text, 1, chunk_start as Word, &data);
+    records = records.wrapping_add(1);
+  }
+
+  push_record(&mut text, 5, records, &[]);
+  push_record(&mut text, 9, entry.unwrap_or_default(), &[]);
+
+  text
+}
+
+fn push_record(text: &mut String, record_type: u8, addr: Word, data: &[Byte]) {
+  let mut bytes = vec![(data.len() + 3) as u8];
+  bytes.extend(addr.to_be_bytes());
+  bytes.extend(data);
+  let checksum = !bytes.iter().fold(0u8, |acc, val| acc.wrapping_add(*val));
+  bytes.push(checksum);
+
+  text.push_str(&format!("S{record_type}"));
+  for byte in bytes {
+    text.push_str(&format!("{byte:02X}"));
+  }
+  text.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod load {
+    use crate::{
+      loaders::{LoadError, srec::load},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_load_data_records_and_start_address() {
+      let mut memory = Generic64kMem::new();
+      let text = "S0030000FC\nS1060600A9018DBC\nS9030600F6\n";
+
+      let info = load(&mut memory, text).unwrap();
+
+      assert_eq!(&memory[0x0600..0x0603], &[0xA9, 0x01, 0x8D]);
+      assert_eq!(info.entry, Some(0x0600));
+      assert_eq!(info.ranges, vec![0x0600..=0x0602]);
+    }
+
+    #[test]
+    fn should_load_data_records_with_wider_addresses_inside_address_space() {
+      let mut memory = Generic64kMem::new();
+      let text = "S205000600EA0A\nS804000600F5\n";
+
+      let info = load(&mut memory, text).unwrap();
+
+      assert_eq!(memory[0x0600], 0xEA);
+      assert_eq!(info.entry, Some(0x0600));
+    }
+
+    #[test]
+    fn should_report_checksum_mismatch_with_line() {
+      let mut memory = Generic64kMem::new();
+      let text = "S0030000FC\nS1060600A9018DBD\nS9030600F6\n";
+
+      assert_eq!(
+        load(&mut memory, text),
+        Err(LoadError::ChecksumMismatch {
+          line: 2,
+          expected: 0xBC,
+          actual: 0xBD
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_address_out_of_range() {
+      let mut memory = Generic64kMem::new();
+      let text = "S205010000EA0F\nS9030000FC\n";
+
+      assert_eq!(
+        load(&mut memory, text),
+        Err(LoadError::AddressOutOfRange {
+          line: 1,
+          addr: 0x10000
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_unsupported_record_type() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, "S4030000FC\n"),
+        Err(LoadError::UnsupportedRecord {
+          line: 1,
+          record_type: 4
+        })
+      );
+    }
+
+    #[test]
+    fn should_report_missing_end_record() {
+      let mut memory = Generic64kMem::new();
+
+      assert_eq!(
+        load(&mut memory, "S1060600A9018DBC\n"),
+        Err(LoadError::MissingEndRecord)
+      );
+    }
+  }
+
+  #[cfg(test)]
+  mod write {
+    use crate::{
+      loaders::srec::{load, write},
+      memory::Generic64kMem,
+    };
+
+    #[test]
+    fn should_write_header_data_count_and_termination_records() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0600, &[0xA9, 0x01, 0x8D]);
+
+      assert_eq!(
+        write(&memory, 0x0600..=0x0602, Some(0x0600)),
+        "S0030000FC\nS1060600A9018DBC\nS5030001FB\nS9030600F6\n"
+      );
+    }
+
+    #[test]
+    fn should_split_data_into_records_that_load_back() {
+      let mut source = Generic64kMem::new();
+      let payload: Vec<u8> = (0..40).collect();
+      source.insert(0x1000, &payload);
+
+      let text = write(&source, 0x1000..=0x1027, Some(0x1000));
+      let mut target = Generic64kMem::new();
+      let info = load(&mut target, &text).unwrap();
+
+      assert_eq!(&target[0x1000..0x1028], payload.as_slice());
+      assert_eq!(info.entry, Some(0x1000));
+    }
+  }
+}
@@ -1,14 +1,61 @@
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+
 use crate::consts::Word;
 
 use super::consts::Byte;
 use std::{
-  cell::Cell,
-  ops::{Index, IndexMut, Range},
+  cell::{Cell, RefCell},
+  ops::{Index, IndexMut, Range, RangeInclusive},
 };
 
+pub mod bus_monitor;
+pub mod heatmap;
+pub mod hexdump;
+pub mod map;
+pub mod shared_ram;
+pub mod snapshot;
+pub mod test_device;
+
 const MAX_MEMORY_KB: usize = 64 * 1024;
+// Faults not taken beyond this are dropped, oldest first.
+const MAX_RECORDED_FAULTS: usize = 1024;
+
+pub trait Memory: IndexMut<Word, Output = Byte> + Index<Word, Output = Byte> {
+  /// Called by the CPU after every cycle, once all bus accesses of the cycle are done.
+  fn end_cycle(&mut self) {}
+
+  /// Returns faults recorded since the last call.
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    Vec::new()
+  }
+
+  /// Read performed by the CPU whose value is discarded.
+  fn dummy_read(&self, addr: Word) {
+    _ = self[addr];
+  }
+
+  /// Read of an opcode, i.e. the first cycle of an instruction.
+  fn fetch_opcode(&self, addr: Word) -> Byte {
+    self[addr]
+  }
+
+  /// Reads a byte without side effects, for debugging tools.
+  fn peek(&self, addr: Word) -> Byte {
+    self[addr]
+  }
+
+  /// Writes a byte without going through the bus, so write protection doesn't apply. Used by
+  /// the loaders and debugging tools.
+  fn poke(&mut self, addr: Word, value: Byte) {
+    self[addr] = value;
+  }
 
-pub trait Memory: IndexMut<Word, Output = Byte> + Index<Word, Output = Byte> {}
+  /// Returns bus accesses recorded since the last call, oldest first. Only memories monitoring
+  /// the bus, like `BusMonitor`, record them.
+  fn take_accesses(&self) -> Vec<BusAccess> {
+    Vec::new()
+  }
+}
 
 #[derive(Copy, Clone)]
 pub enum Operation {
@@ -16,8 +63,58 @@ pub enum Operation {
   Write(Word),
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BusAccessKind {
+  Read,
+  Write,
+}
+
+/// Data access made by the CPU. For reads `old` and `new` are both the value read.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BusAccess {
+  pub kind: BusAccessKind,
+  pub addr: Word,
+  pub old: Byte,
+  pub new: Byte,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MemoryFault {
+  ProtectedWrite(Word),
+  UninitializedRead(Word),
+}
+
+impl MemoryFault {
+  pub fn addr(&self) -> Word {
+    match self {
+      MemoryFault::ProtectedWrite(addr) | MemoryFault::UninitializedRead(addr) => *addr,
+    }
+  }
+
+  fn offset_by(self, base: Word) -> Self {
+    match self {
+      MemoryFault::ProtectedWrite(addr) => MemoryFault::ProtectedWrite(addr.wrapping_add(base)),
+      MemoryFault::UninitializedRead(addr) => {
+        MemoryFault::UninitializedRead(addr.wrapping_add(base))
+      }
+    }
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WriteProtection {
+  /// Writes are dropped, as on real ROM.
+  Ignore,
+  /// Writes are dropped and recorded as `MemoryFault::ProtectedWrite`.
+  Fault,
+}
+
 pub struct Generic64kMem {
   last_op: Cell<Option<Operation>>,
+  protected: Vec<(RangeInclusive<Word>, WriteProtection)>,
+  faults: RefCell<AllocRingBuffer<MemoryFault>>,
+  write_sink: Byte,
+  initialized: Option<Vec<bool>>,
   pub data: Vec<Byte>,
 }
 
@@ -31,22 +128,80 @@ impl Generic64kMem {
   pub fn new() -> Self {
     Generic64kMem {
       last_op: Cell::new(None),
+      protected: Vec::new(),
+      faults: RefCell::new(AllocRingBuffer::new(MAX_RECORDED_FAULTS)),
+      write_sink: 0,
+      initialized: None,
       data: vec![0; MAX_MEMORY_KB],
     }
   }
 
+  /// Protects `range` against writes through the bus. `store`, `insert` and `poke` still
+  /// modify it, so ROM images can be loaded before or after protecting.
+  pub fn protect(&mut self, range: RangeInclusive<Word>, protection: WriteProtection) {
+    self.protected.push((range, protection));
+  }
+
+  /// Starts reporting reads of bytes that were not written, stored or inserted since.
+  /// Everything is considered uninitialized at this point.
+  pub fn track_uninitialized(&mut self) {
+    self.initialized = Some(vec![false; MAX_MEMORY_KB]);
+  }
+
+  fn mark_initialized(&mut self, range: Range<usize>) {
+    if let Some(initialized) = &mut self.initialized {
+      initialized[range].fill(true);
+    }
+  }
+
+  fn readable(&self, addr: Word) -> &Byte {
+    if let Some(initialized) = &self.initialized
+      && !initialized[addr as usize]
+    {
+      self
+        .faults
+        .borrow_mut()
+        .push(MemoryFault::UninitializedRead(addr));
+    }
+    &self.data[addr as usize]
+  }
+
+  fn writable(&mut self, addr: Word) -> &mut Byte {
+    let protection = self
+      .protected
+      .iter()
+      .rev()
+      .find(|(range, _)| range.contains(&addr))
+      .map(|(_, protection)| *protection);
+
+    match protection {
+      None => {
+        self.mark_initialized(addr as usize..addr as usize + 1);
+        &mut self.data[addr as usize]
+      }
+      Some(protection) => {
+        if protection == WriteProtection::Fault {
+          self
+            .faults
+            .borrow_mut()
+            .push(MemoryFault::ProtectedWrite(addr));
+        }
+        &mut self.write_sink
+      }
+    }
+  }
+
   pub fn store(&mut self, payload: &[(Word, Byte)]) {
     for (address, value) in payload {
       let idx: usize = (*address).into();
       self.data[idx] = *value;
+      self.mark_initialized(idx..idx + 1);
     }
   }
   pub fn insert(&mut self, addr: Word, payload: &[Byte]) {
-    let mut tgt_addr = addr as usize;
-    for value in payload {
-      self.data[tgt_addr] = *value;
-      tgt_addr += 1;
-    }
+    let start = addr as usize;
+    self.data[start..start + payload.len()].copy_from_slice(payload);
+    self.mark_initialized(start..start + payload.len());
   }
 
   pub fn get_last_operation(&self) -> Option<Operation> {
@@ -54,15 +209,30 @@ impl Generic64kMem {
   }
 }
 
-impl Memory for Generic64kMem {}
+impl Memory for Generic64kMem {
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    self.faults.borrow_mut().drain().collect()
+  }
+
+  fn dummy_read(&self, addr: Word) {
+    self.last_op.set(Some(Operation::Read(addr)));
+  }
+
+  fn peek(&self, addr: Word) -> Byte {
+    self.data[addr as usize]
+  }
+
+  fn poke(&mut self, addr: Word, value: Byte) {
+    self.store(&[(addr, value)]);
+  }
+}
 
 impl Index<Word> for Generic64kMem {
   type Output = Byte;
 
   fn index(&self, idx: Word) -> &Self::Output {
-    let mem_address: usize = idx.into();
     self.last_op.set(Some(Operation::Read(idx)));
-    &self.data[mem_address]
+    self.readable(idx)
   }
 }
 
@@ -78,9 +248,8 @@ impl Index<Range<Word>> for Generic64kMem {
 
 impl IndexMut<Word> for Generic64kMem {
   fn index_mut(&mut self, idx: Word) -> &mut Self::Output {
-    let mem_address: usize = idx.into();
     self.last_op.set(Some(Operation::Write(idx)));
-    &mut self.data[mem_address]
+    self.writable(idx)
   }
 }
 
@@ -92,3 +261,129 @@ impl From<&[(Word, Byte)]> for Generic64kMem {
     res
   }
 }
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod protect {
+    use crate::memory::{Generic64kMem, Memory, MemoryFault, WriteProtection};
+
+    #[test]
+    fn should_ignore_writes_to_protected_range() {
+      let mut uut = Generic64kMem::new();
+      uut.insert(0xE000, &[0x4C]);
+      uut.protect(0xE000..=0xFFFF, WriteProtection::Ignore);
+
+      uut[0xE000] = 0x00;
+      uut[0xDFFF] = 0x01;
+
+      assert_eq!(uut[0xE000], 0x4C);
+      assert_eq!(uut[0xDFFF], 0x01);
+      assert!(uut.take_faults().is_empty());
+    }
+
+    #[test]
+    fn should_record_fault_for_writes_to_range_protected_with_fault() {
+      let mut uut = Generic64kMem::new();
+      uut.protect(0xE000..=0xFFFF, WriteProtection::Fault);
+
+      uut[0xE010] = 0x00;
+
+      assert_eq!(uut.take_faults(), vec![MemoryFault::ProtectedWrite(0xE010)]);
+      assert!(uut.take_faults().is_empty());
+    }
+
+    #[test]
+    fn should_keep_only_last_faults_when_not_taken() {
+      let mut uut = Generic64kMem::new();
+      uut.protect(0xE000..=0xFFFF, WriteProtection::Fault);
+
+      for addr in 0xE000..=0xFFFF {
+        uut[addr] = 0x00;
+      }
+
+      let faults = uut.take_faults();
+      assert_eq!(faults.len(), 1024);
+      assert_eq!(faults.last(), Some(&MemoryFault::ProtectedWrite(0xFFFF)));
+    }
+
+    #[test]
+    fn should_not_mark_protected_bytes_initialized_by_dropped_writes() {
+      let mut uut = Generic64kMem::new();
+      uut.track_uninitialized();
+      uut.protect(0xE000..=0xFFFF, WriteProtection::Ignore);
+
+      uut[0xE000] = 0x01;
+      _ = uut[0xE000];
+
+      assert_eq!(
+        uut.take_faults(),
+        vec![MemoryFault::UninitializedRead(0xE000)]
+      );
+    }
+
+    #[test]
+    fn should_allow_loading_protected_range_directly() {
+      let mut uut = Generic64kMem::new();
+      uut.protect(0xE000..=0xFFFF, WriteProtection::Fault);
+
+      uut.insert(0xE000, &[0xEA]);
+
+      assert_eq!(uut[0xE000], 0xEA);
+      assert!(uut.take_faults().is_empty());
+    }
+  }
+
+  #[cfg(test)]
+  mod track_uninitialized {
+    use crate::memory::{Generic64kMem, Memory, MemoryFault};
+
+    #[test]
+    fn should_report_reads_of_bytes_never_written() {
+      let mut uut = Generic64kMem::new();
+      uut.track_uninitialized();
+
+      _ = uut[0x0200];
+
+      assert_eq!(
+        uut.take_faults(),
+        vec![MemoryFault::UninitializedRead(0x0200)]
+      );
+    }
+
+    #[test]
+    fn should_not_report_reads_of_written_stored_or_inserted_bytes() {
+      let mut uut = Generic64kMem::new();
+      uut.track_uninitialized();
+
+      uut[0x0200] = 0x00;
+      uut.store(&[(0x0300, 0x00)]);
+      uut.insert(0x0400, &[0x00, 0x00]);
+      _ = uut[0x0200];
+      _ = uut[0x0300];
+      _ = uut[0x0401];
+
+      assert!(uut.take_faults().is_empty());
+    }
+
+    #[test]
+    fn should_not_report_dummy_reads_and_peeks() {
+      let mut uut = Generic64kMem::new();
+      uut.track_uninitialized();
+
+      uut.dummy_read(0x0200);
+      uut.peek(0x0200);
+
+      assert!(uut.take_faults().is_empty());
+    }
+
+    #[test]
+    fn should_not_report_anything_when_not_tracking() {
+      let uut = Generic64kMem::new();
+
+      _ = uut[0x0200];
+
+      assert!(uut.take_faults().is_empty());
+    }
+  }
+}
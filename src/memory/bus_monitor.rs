@@ -0,0 +1,231 @@
+use std::{
+  cell::{Cell, RefCell},
+  ops::{Index, IndexMut},
+};
+
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+
+use crate::{
+  consts::{Byte, DEFAULT_BUS_ACCESS_CAPACITY, Word},
+  memory::{BusAccess, BusAccessKind, Memory, MemoryFault},
+};
+
+/// Wraps a memory and records the data accesses of the CPU, to be taken with
+/// `Memory::take_accesses`, e.g. by the watchpoints of a `Debugger`. Opcode fetches and peeks
+/// are not recorded, dummy reads are. Only the last accesses are kept until they're taken, so
+/// running without taking them doesn't grow the monitor.
+pub struct BusMonitor<M: Memory> {
+  inner: M,
+  accesses: RefCell<AllocRingBuffer<BusAccess>>,
+  // address and previous value of a write whose value hasn't been assigned yet
+  pending_write: Cell<Option<(Word, Byte)>>,
+}
+
+impl<M: Memory> BusMonitor<M> {
+  pub fn new(inner: M) -> Self {
+    Self::with_capacity(inner, DEFAULT_BUS_ACCESS_CAPACITY)
+  }
+
+  /// Creates a monitor keeping up to `capacity` last accesses.
+  pub fn with_capacity(inner: M, capacity: usize) -> Self {
+    BusMonitor {
+      inner,
+      accesses: RefCell::new(AllocRingBuffer::new(capacity.max(1))),
+      pending_write: Cell::new(None),
+    }
+  }
+
+  pub fn inner(&self) -> &M {
+    &self.inner
+  }
+
+  pub fn inner_mut(&mut self) -> &mut M {
+    self.complete_write();
+    &mut self.inner
+  }
+
+  pub fn into_inner(self) -> M {
+    self.inner
+  }
+
+  fn record_read(&self, addr: Word, value: Byte) {
+    self.complete_write();
+    self.accesses.borrow_mut().push(BusAccess {
+      kind: BusAccessKind::Read,
+      addr,
+      old: value,
+      new: value,
+    });
+  }
+
+  // The written value is only known once the caller assigns it, so the write is recorded on
+  // the next access or at the end of the cycle.
+  fn complete_write(&self) {
+    let Some((addr, old)) = self.pending_write.take() else {
+      return;
+    };
+
+    self.accesses.borrow_mut().push(BusAccess {
+      kind: BusAccessKind::Write,
+      addr,
+      old,
+      new: self.inner.peek(addr),
+    });
+  }
+}
+
+impl<M: Memory> Memory for BusMonitor<M> {
+  fn end_cycle(&mut self) {
+    self.inner.end_cycle();
+    self.complete_write();
+  }
+
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    self.inner.take_faults()
+  }
+
+  fn dummy_read(&self, addr: Word) {
+    self.complete_write();
+    self.inner.dummy_read(addr);
+    self.record_read(addr, self.inner.peek(addr));
+  }
+
+  fn fetch_opcode(&self, addr: Word) -> Byte {
+    self.complete_write();
+    self.inner.fetch_opcode(addr)
+  }
+
+  fn peek(&self, addr: Word) -> Byte {
+    self.inner.peek(addr)
+  }
+
+  fn poke(&mut self, addr: Word, value: Byte) {
+    self.complete_write();
+    self.inner.poke(addr, value);
+  }
+
+  fn take_accesses(&self) -> Vec<BusAccess> {
+    self.complete_write();
+    self.accesses.borrow_mut().drain().collect()
+  }
+}
+
+impl<M: Memory> Index<Word> for BusMonitor<M> {
+  type Output = Byte;
+
+  fn index(&self, addr: Word) -> &Self::Output {
+    self.complete_write();
+    let value = &self.inner[addr];
+    self.record_read(addr, *value);
+    value
+  }
+}
+
+impl<M: Memory> IndexMut<Word> for BusMonitor<M> {
+  fn index_mut(&mut self, addr: Word) -> &mut Self::Output {
+    self.complete_write();
+    self.pending_write.set(Some((addr, self.inner.peek(addr))));
+    &mut self.inner[addr]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod take_accesses {
+    use crate::{
+      consts::{Byte, Word},
+      cpu::{CPU, utils::execute_next_instruction},
+      memory::{
+        BusAccess, BusAccessKind, Generic64kMem, Memory, WriteProtection, bus_monitor::BusMonitor,
+        map::MemoryMap,
+      },
+    };
+
+    fn access(kind: BusAccessKind, addr: Word, old: Byte, new: Byte) -> BusAccess {
+      BusAccess {
+        kind,
+        addr,
+        old,
+        new,
+      }
+    }
+
+    #[test]
+    fn should_record_reads_and_writes_with_old_and_new_values() {
+      let mut inner = Generic64kMem::new();
+      // LDA $0200, STA $0201
+      inner.insert(0x0600, &[0xAD, 0x00, 0x02, 0x8D, 0x01, 0x02]);
+      inner.insert(0x0200, &[0x42, 0x07]);
+      inner.insert(0xFFFC, &[0x00, 0x06]);
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&inner);
+      let mut uut = BusMonitor::new(inner);
+
+      execute_next_instruction(&mut cpu, &mut uut, None);
+      execute_next_instruction(&mut cpu, &mut uut, None);
+
+      assert_eq!(
+        uut.take_accesses(),
+        [
+          access(BusAccessKind::Read, 0x0601, 0x00, 0x00),
+          access(BusAccessKind::Read, 0x0602, 0x02, 0x02),
+          access(BusAccessKind::Read, 0x0200, 0x42, 0x42),
+          access(BusAccessKind::Read, 0x0604, 0x01, 0x01),
+          access(BusAccessKind::Read, 0x0605, 0x02, 0x02),
+          access(BusAccessKind::Write, 0x0201, 0x07, 0x42),
+        ]
+      );
+      assert!(uut.take_accesses().is_empty());
+    }
+
+    #[test]
+    fn should_keep_only_last_accesses_up_to_capacity() {
+      let mut uut = BusMonitor::with_capacity(Generic64kMem::new(), 2);
+
+      for addr in 0..10 {
+        uut[addr] = addr as Byte;
+      }
+
+      assert_eq!(
+        uut.take_accesses(),
+        [
+          access(BusAccessKind::Write, 0x0008, 0x00, 0x08),
+          access(BusAccessKind::Write, 0x0009, 0x00, 0x09),
+        ]
+      );
+    }
+
+    #[test]
+    fn should_record_latched_writes_of_memory_map() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0010, &[0x01]);
+      let mut map = MemoryMap::new();
+      map.map(0x0000..=0xFFFF, ram);
+      let mut uut = BusMonitor::new(map);
+
+      uut[0x0010] = 0x02;
+      uut.end_cycle();
+
+      assert_eq!(
+        uut.take_accesses(),
+        [access(BusAccessKind::Write, 0x0010, 0x01, 0x02)]
+      );
+    }
+
+    #[test]
+    fn should_record_write_leaving_protected_memory_unchanged() {
+      let mut inner = Generic64kMem::new();
+      inner.insert(0xE000, &[0x55]);
+      inner.protect(0xE000..=0xFFFF, WriteProtection::Ignore);
+      let mut uut = BusMonitor::new(inner);
+
+      uut[0xE000] = 0xAA;
+
+      assert_eq!(
+        uut.take_accesses(),
+        [access(BusAccessKind::Write, 0xE000, 0x55, 0x55)]
+      );
+    }
+  }
+}
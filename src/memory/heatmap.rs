@@ -0,0 +1,297 @@
+use std::{
+  cell::Cell,
+  ops::{Index, IndexMut},
+};
+
+use crate::{
+  consts::{Byte, Word},
+  memory::{BusAccess, Memory, MemoryFault},
+};
+
+const ADDRESSES: usize = 64 * 1024;
+const SIDE: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessKind {
+  Read,
+  Write,
+  Execute,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AccessCounts {
+  pub reads: u32,
+  pub writes: u32,
+  pub executes: u32,
+}
+
+impl AccessCounts {
+  fn get(&self, kind: AccessKind) -> u32 {
+    match kind {
+      AccessKind::Read => self.reads,
+      AccessKind::Write => self.writes,
+      AccessKind::Execute => self.executes,
+    }
+  }
+}
+
+/// Wraps a memory and counts accesses per address. Opcode fetches are counted as executes
+/// only, operand and data reads as reads. Dummy reads are counted, peeks are not.
+pub struct AccessCounter<M: Memory> {
+  inner: M,
+  counts: Vec<Cell<AccessCounts>>,
+}
+
+impl<M: Memory> AccessCounter<M> {
+  pub fn new(inner: M) -> Self {
+    AccessCounter {
+      inner,
+      counts: vec![Cell::new(AccessCounts::default()); ADDRESSES],
+    }
+  }
+
+  pub fn inner(&self) -> &M {
+    &self.inner
+  }
+
+  pub fn inner_mut(&mut self) -> &mut M {
+    &mut self.inner
+  }
+
+  pub fn into_inner(self) -> M {
+    self.inner
+  }
+
+  pub fn counts(&self, addr: Word) -> AccessCounts {
+    self.counts[addr as usize].get()
+  }
+
+  pub fn reset(&mut self) {
+    self.counts.fill(Cell::new(AccessCounts::default()));
+  }
+
+  /// Binary PGM of a single access type. Each row is a page, each pixel an address.
+  pub fn to_pgm(&self, kind: AccessKind) -> Vec<Byte> {
+    let shades = self.shades(kind);
+    let mut image = format!("P5\n{SIDE} {SIDE}\n255\n").into_bytes();
+    image.extend(shades);
+    image
+  }
+
+  /// Binary PPM with reads in the red, writes in the green and executes in the blue channel.
+  pub fn to_ppm(&self) -> Vec<Byte> {
+    let reads = self.shades(AccessKind::Read);
+    let writes = self.shades(AccessKind::Write);
+    let executes = self.shades(AccessKind::Execute);
+
+    let mut image = format!("P6\n{SIDE} {SIDE}\n255\n").into_bytes();
+    for idx in 0..ADDRESSES {
+      image.extend([reads[idx], writes[idx], executes[idx]]);
+    }
+    image
+  }
+
+  /// One line per accessed address.
+  pub fn to_csv(&self) -> String {
+    let mut csv = String::from("address,reads,writes,executes\n");
+    for (addr, counts) in self.counts.iter().enumerate() {
+      let counts = counts.get();
+      if counts != AccessCounts::default() {
+        csv.push_str(&format!(
+          "{addr:#06X},{},{},{}\n",
+          counts.reads, counts.writes, counts.executes
+        ));
+      }
+    }
+    csv
+  }
+
+  // Counts are scaled logarithmically against the hottest address, so that rarely touched
+  // addresses remain visible next to tight loops.
+  fn shades(&self, kind: AccessKind) -> Vec<Byte> {
+    let max = self
+      .counts
+      .iter()
+      .map(|counts| counts.get().get(kind))
+      .max()
+      .unwrap_or_default();
+    if max == 0 {
+      return vec![0; ADDRESSES];
+    }
+
+    let scale = 255.0 / (max as f64).ln_1p();
+    self
+      .counts
+      .iter()
+      .map(|counts| ((counts.get().get(kind) as f64).ln_1p() * scale).round() as Byte)
+      .collect()
+  }
+
+  fn count(&self, addr: Word, kind: AccessKind) {
+    let cell = &self.counts[addr as usize];
+    let mut counts = cell.get();
+    let counter = match kind {
+      AccessKind::Read => &mut counts.reads,
+      AccessKind::Write => &mut counts.writes,
+      AccessKind::Execute => &mut counts.executes,
+    };
+    *counter = counter.saturating_add(1);
+    cell.set(counts);
+  }
+}
+
+impl<M: Memory> Memory for AccessCounter<M> {
+  fn end_cycle(&mut self) {
+    self.inner.end_cycle();
+  }
+
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    self.inner.take_faults()
+  }
+
+  fn dummy_read(&self, addr: Word) {
+    self.count(addr, AccessKind::Read);
+    self.inner.dummy_read(addr);
+  }
+
+  fn fetch_opcode(&self, addr: Word) -> Byte {
+    self.count(addr, AccessKind::Execute);
+    self.inner.fetch_opcode(addr)
+  }
+
+  fn peek(&self, addr: Word) -> Byte {
+    self.inner.peek(addr)
+  }
+
+  fn poke(&mut self, addr: Word, value: Byte) {
+    self.inner.poke(addr, value);
+  }
+
+  fn take_accesses(&self) -> Vec<BusAccess> {
+    self.inner.take_accesses()
+  }
+}
+
+impl<M: Memory> Index<Word> for AccessCounter<M> {
+  type Output = Byte;
+
+  fn index(&self, addr: Word) -> &Self::Output {
+    self.count(addr, AccessKind::Read);
+    &self.inner[addr]
+  }
+}
+
+impl<M: Memory> IndexMut<Word> for AccessCounter<M> {
+  fn index_mut(&mut self, addr: Word) -> &mut Self::Output {
+    self.count(addr, AccessKind::Write);
+    &mut self.inner[addr]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    cpu::{CPU, utils::execute_next_instruction},
+    memory::{Generic64kMem, heatmap::AccessCounter},
+  };
+
+  // LDA $0200, STA $0201
+  fn run_program() -> AccessCounter<Generic64kMem> {
+    let mut inner = Generic64kMem::new();
+    inner.insert(0x0600, &[0xAD, 0x00, 0x02, 0x8D, 0x01, 0x02]);
+    inner.insert(0xFFFC, &[0x00, 0x06]);
+    let mut cpu = CPU::new_nmos();
+    cpu.reset(&inner);
+    let mut memory = AccessCounter::new(inner);
+
+    execute_next_instruction(&mut cpu, &mut memory, None);
+    execute_next_instruction(&mut cpu, &mut memory, None);
+    memory
+  }
+
+  #[cfg(test)]
+  mod counts {
+    use super::run_program;
+    use crate::memory::heatmap::AccessCounts;
+
+    #[test]
+    fn should_count_reads_writes_and_executes_per_address() {
+      let memory = run_program();
+
+      assert_eq!(
+        memory.counts(0x0600),
+        AccessCounts {
+          reads: 0,
+          writes: 0,
+          executes: 1
+        }
+      );
+      assert_eq!(memory.counts(0x0601).reads, 1);
+      assert_eq!(memory.counts(0x0200).reads, 1);
+      assert_eq!(memory.counts(0x0201).writes, 1);
+      assert_eq!(memory.counts(0x0202), AccessCounts::default());
+    }
+  }
+
+  #[cfg(test)]
+  mod to_ppm {
+    use super::run_program;
+
+    #[test]
+    fn should_render_one_pixel_per_address_with_channel_per_access_kind() {
+      let memory = run_program();
+
+      let image = memory.to_ppm();
+      let header = b"P6\n256 256\n255\n";
+      let pixel = |addr: usize| &image[header.len() + addr * 3..header.len() + addr * 3 + 3];
+
+      assert!(image.starts_with(header));
+      assert_eq!(image.len(), header.len() + 256 * 256 * 3);
+      assert_eq!(pixel(0x0600), &[0, 0, 255]);
+      assert_eq!(pixel(0x0201), &[0, 255, 0]);
+      assert_eq!(pixel(0x0200), &[255, 0, 0]);
+      assert_eq!(pixel(0x0300), &[0, 0, 0]);
+    }
+  }
+
+  #[cfg(test)]
+  mod to_pgm {
+    use super::run_program;
+    use crate::memory::heatmap::AccessKind;
+
+    #[test]
+    fn should_render_single_access_kind() {
+      let memory = run_program();
+
+      let image = memory.to_pgm(AccessKind::Write);
+      let header = b"P5\n256 256\n255\n";
+
+      assert!(image.starts_with(header));
+      assert_eq!(image[header.len() + 0x0201], 255);
+      assert_eq!(image[header.len() + 0x0200], 0);
+    }
+  }
+
+  #[cfg(test)]
+  mod to_csv {
+    use super::run_program;
+
+    #[test]
+    fn should_list_accessed_addresses_only() {
+      let memory = run_program();
+
+      assert_eq!(
+        memory.to_csv(),
+        "address,reads,writes,executes\n\
+         0x0200,1,0,0\n\
+         0x0201,0,1,0\n\
+         0x0600,0,0,1\n\
+         0x0601,1,0,0\n\
+         0x0602,1,0,0\n\
+         0x0603,0,0,1\n\
+         0x0604,1,0,0\n\
+         0x0605,1,0,0\n"
+      );
+    }
+  }
+}
@@ -0,0 +1,189 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::{
+  consts::{Byte, Word},
+  cpu::debugger::Symbols,
+  memory::Memory,
+};
+
+const DEFAULT_WIDTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Charset {
+  Ascii,
+  /// Commodore PETSCII, upper case/graphics set.
+  Petscii,
+  /// Atari ATASCII. Inverse video characters are shown as their normal counterparts.
+  Atascii,
+}
+
+impl Charset {
+  pub fn to_char(&self, value: Byte) -> char {
+    match self {
+      Charset::Ascii => match value {
+        0x20..=0x7E => value as char,
+        _ => '.',
+      },
+      Charset::Petscii => match value {
+        0x20..=0x5B | 0x5D => value as char,
+        0x5C => '£',
+        0x5E => '↑',
+        0x5F => '←',
+        0xA0 => ' ',
+        _ => '.',
+      },
+      Charset::Atascii => match value & 0x7F {
+        value @ (0x20..=0x5F | 0x61..=0x7A | 0x7C) => value as char,
+        _ => '.',
+      },
+    }
+  }
+}
+
+/// Hex and character dump of a memory range, read with `Memory::peek`. Lines are `width` bytes
+/// long, and a new labelled line is started at every address that has a symbol.
+pub struct HexDump<'a> {
+  memory: &'a dyn Memory,
+  range: RangeInclusive<Word>,
+  width: usize,
+  charset: Charset,
+  symbols: Option<&'a dyn Symbols>,
+}
+
+impl<'a> HexDump<'a> {
+  pub fn new(memory: &'a dyn Memory, range: RangeInclusive<Word>) -> Self {
+    HexDump {
+      memory,
+      range,
+      width: DEFAULT_WIDTH,
+      charset: Charset::Ascii,
+      symbols: None,
+    }
+  }
+
+  pub fn with_width(mut self, width: usize) -> Self {
+    self.width = width.max(1);
+    self
+  }
+
+  pub fn with_charset(mut self, charset: Charset) -> Self {
+    self.charset = charset;
+    self
+  }
+
+  pub fn with_symbols(mut self, symbols: &'a dyn Symbols) -> Self {
+    self.symbols = Some(symbols);
+    self
+  }
+
+  fn symbol(&self, addr: Word) -> Option<String> {
+    self.symbols.and_then(|symbols| symbols.get(&addr))
+  }
+
+  fn write_line(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+    start: Word,
+    values: &[Byte],
+  ) -> std::fmt::Result {
+    write!(f, "{start:04X}:")?;
+    for value in values {
+      write!(f, " {value:02X}")?;
+    }
+    let padding = (self.width - values.len()) * 3;
+    let text: String = values
+      .iter()
+      .map(|value| self.charset.to_char(*value))
+      .collect();
+    writeln!(f, "{:padding$}  |{text}|", "")
+  }
+}
+
+impl Display for HexDump<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut line_start = *self.range.start();
+    let mut values = Vec::with_capacity(self.width);
+
+    for addr in self.range.clone() {
+      let symbol = self.symbol(addr);
+      if !values.is_empty() && (values.len() == self.width || symbol.is_some()) {
+        self.write_line(f, line_start, &values)?;
+        values.clear();
+      }
+      if values.is_empty() {
+        line_start = addr;
+        if let Some(symbol) = symbol {
+          writeln!(f, "{symbol}:")?;
+        }
+      }
+      values.push(self.memory.peek(addr));
+    }
+
+    if !values.is_empty() {
+      self.write_line(f, line_start, &values)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod fmt {
+    use crate::{
+      cpu::debugger::symbol_table::SymbolTable,
+      memory::{
+        Generic64kMem,
+        hexdump::{Charset, HexDump},
+      },
+    };
+
+    #[test]
+    fn should_render_hex_and_ascii_columns() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0200, b"Hello, 6502!\x00\x01");
+
+      let dump = HexDump::new(&memory, 0x0200..=0x020D).with_width(8);
+
+      assert_eq!(
+        dump.to_string(),
+        "0200: 48 65 6C 6C 6F 2C 20 36  |Hello, 6|\n\
+         0208: 35 30 32 21 00 01        |502!..|\n"
+      );
+    }
+
+    #[test]
+    fn should_start_labelled_line_at_symbol() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0600, &[0xA9, 0x01, 0x60, 0xEA]);
+      let mut symbols = SymbolTable::new();
+      symbols.insert(0x0600, "main");
+      symbols.insert(0x0603, "loop");
+
+      let dump = HexDump::new(&memory, 0x0600..=0x0603)
+        .with_width(4)
+        .with_symbols(&symbols);
+
+      assert_eq!(
+        dump.to_string(),
+        "main:\n\
+         0600: A9 01 60     |..`|\n\
+         loop:\n\
+         0603: EA           |.|\n"
+      );
+    }
+
+    #[test]
+    fn should_map_petscii_and_atascii_characters() {
+      let mut memory = Generic64kMem::new();
+      memory.insert(0x0400, &[0x48, 0x49, 0x5C, 0xC1]);
+      memory.insert(0x0500, &[0x48, 0xC9, 0x60, 0x7C]);
+
+      let petscii = HexDump::new(&memory, 0x0400..=0x0403).with_charset(Charset::Petscii);
+      let atascii = HexDump::new(&memory, 0x0500..=0x0503).with_charset(Charset::Atascii);
+
+      assert!(petscii.to_string().ends_with("|HI£.|\n"));
+      assert!(atascii.to_string().ends_with("|HI.||\n"));
+    }
+  }
+}
@@ -0,0 +1,396 @@
+use std::{
+  cell::{Cell, RefCell},
+  ops::{Index, IndexMut, RangeInclusive},
+};
+
+use crate::{
+  consts::{Byte, Word},
+  memory::{Generic64kMem, Memory, MemoryFault},
+};
+
+// Index has to hand out a reference, so values computed during a read are returned
+// as references into this table.
+static BUS_VALUES: [Byte; 256] = {
+  let mut values = [0; 256];
+  let mut idx = 0;
+  while idx < values.len() {
+    values[idx] = idx as Byte;
+    idx += 1;
+  }
+  values
+};
+
+/// Anything that can be placed in a `MemoryMap`. Addresses are passed as offsets from the
+/// start of the mapped range.
+pub trait Mappable {
+  fn read(&mut self, offset: Word) -> Byte;
+  fn write(&mut self, offset: Word, value: Byte);
+
+  /// Bits of the data bus driven by the region during a read of `offset`. Bits that are not
+  /// driven keep the last value seen on the bus.
+  fn driven_bits(&self, _offset: Word) -> Byte {
+    0xFF
+  }
+
+  /// Faults recorded since the last call, with addresses as offsets into the region.
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    Vec::new()
+  }
+
+  /// Read whose value the CPU discards. Defaults to a regular read.
+  fn dummy_read(&mut self, offset: Word) -> Byte {
+    self.read(offset)
+  }
+
+  /// Reads without side effects. Defaults to a regular read, devices with read side effects
+  /// should override it.
+  fn peek(&mut self, offset: Word) -> Byte {
+    self.read(offset)
+  }
+
+  /// Writes without going through the bus, e.g. into ROM. Defaults to a regular write.
+  fn poke(&mut self, offset: Word, value: Byte) {
+    self.write(offset, value);
+  }
+}
+
+impl Mappable for Generic64kMem {
+  fn read(&mut self, offset: Word) -> Byte {
+    self[offset]
+  }
+
+  fn dummy_read(&mut self, offset: Word) -> Byte {
+    Memory::peek(self, offset)
+  }
+
+  fn peek(&mut self, offset: Word) -> Byte {
+    Memory::peek(self, offset)
+  }
+
+  fn write(&mut self, offset: Word, value: Byte) {
+    *self.writable(offset) = value;
+  }
+
+  fn poke(&mut self, offset: Word, value: Byte) {
+    Memory::poke(self, offset, value);
+  }
+
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    Memory::take_faults(self)
+  }
+}
+
+struct Region {
+  range: RangeInclusive<Word>,
+  handler: RefCell<Box<dyn Mappable>>,
+}
+
+/// Address space composed of regions. Reads of unmapped addresses return the last value
+/// driven on the data bus (open bus), writes to them are dropped.
+pub struct MemoryMap {
+  regions: Vec<Region>,
+  bus: Cell<Byte>,
+  pending_write: Cell<Option<Word>>,
+  write_latch: Byte,
+}
+
+impl Default for MemoryMap {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl MemoryMap {
+  pub fn new() -> Self {
+    MemoryMap {
+      regions: Vec::new(),
+      bus: Cell::new(0),
+      pending_write: Cell::new(None),
+      write_latch: 0,
+    }
+  }
+
+  /// Maps `handler` at `range`. Regions mapped later take precedence over overlapping ones.
+  pub fn map<T: Mappable + 'static>(&mut self, range: RangeInclusive<Word>, handler: T) {
+    self.flush();
+    self.regions.push(Region {
+      range,
+      handler: RefCell::new(Box::new(handler)),
+    });
+  }
+
+  pub fn bus_value(&self) -> Byte {
+    self.flush();
+    self.bus.get()
+  }
+
+  /// Delivers a write still held in the latch to its region.
+  pub fn flush(&self) {
+    let Some(addr) = self.pending_write.take() else {
+      return;
+    };
+
+    let value = self.write_latch;
+    self.bus.set(value);
+    if let Some(region) = self.region_at(addr) {
+      let offset = addr - region.range.start();
+      region.handler.borrow_mut().write(offset, value);
+    }
+  }
+
+  fn read(&self, addr: Word, dummy: bool) -> Byte {
+    self.flush();
+
+    if let Some(region) = self.region_at(addr) {
+      let offset = addr - region.range.start();
+      let mut handler = region.handler.borrow_mut();
+      let value = if dummy {
+        handler.dummy_read(offset)
+      } else {
+        handler.read(offset)
+      };
+      let driven_bits = handler.driven_bits(offset);
+      self
+        .bus
+        .set((value & driven_bits) | (self.bus.get() & !driven_bits));
+    }
+
+    self.bus.get()
+  }
+
+  fn region_at(&self, addr: Word) -> Option<&Region> {
+    self
+      .regions
+      .iter()
+      .rev()
+      .find(|region| region.range.contains(&addr))
+  }
+}
+
+impl Memory for MemoryMap {
+  fn end_cycle(&mut self) {
+    self.flush();
+  }
+
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    self.flush();
+    self
+      .regions
+      .iter()
+      .flat_map(|region| {
+        let start = *region.range.start();
+        region
+          .handler
+          .borrow()
+          .take_faults()
+          .into_iter()
+          .map(move |fault| fault.offset_by(start))
+      })
+      .collect()
+  }
+
+  fn dummy_read(&self, addr: Word) {
+    self.read(addr, true);
+  }
+
+  // Unmapped addresses peek as the current bus value, without changing it.
+  fn peek(&self, addr: Word) -> Byte {
+    self.flush();
+    match self.region_at(addr) {
+      Some(region) => {
+        let offset = addr - region.range.start();
+        region.handler.borrow_mut().peek(offset)
+      }
+      None => self.bus.get(),
+    }
+  }
+
+  // Writes to unmapped addresses are dropped, without touching the bus.
+  fn poke(&mut self, addr: Word, value: Byte) {
+    self.flush();
+    if let Some(region) = self.region_at(addr) {
+      let offset = addr - region.range.start();
+      region.handler.borrow_mut().poke(offset, value);
+    }
+  }
+}
+
+impl Index<Word> for MemoryMap {
+  type Output = Byte;
+
+  fn index(&self, addr: Word) -> &Self::Output {
+    &BUS_VALUES[self.read(addr, false) as usize]
+  }
+}
+
+impl IndexMut<Word> for MemoryMap {
+  // The value is not known until the caller assigns it, so the write is latched and
+  // delivered on the next access or at the end of the cycle. The latch starts from the peeked
+  // contents, so compound assignments see the current value.
+  fn index_mut(&mut self, addr: Word) -> &mut Self::Output {
+    self.flush();
+    self.write_latch = Memory::peek(self, addr);
+    self.pending_write.set(Some(addr));
+
+    &mut self.write_latch
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    consts::{Byte, Word},
+    memory::{
+      Generic64kMem, Memory, MemoryFault, WriteProtection,
+      map::{Mappable, MemoryMap},
+    },
+  };
+
+  struct LowNibbleDevice {
+    value: Byte,
+  }
+
+  impl Mappable for LowNibbleDevice {
+    fn read(&mut self, _offset: Word) -> Byte {
+      self.value
+    }
+
+    fn write(&mut self, _offset: Word, value: Byte) {
+      self.value = value;
+    }
+
+    fn driven_bits(&self, _offset: Word) -> Byte {
+      0x0F
+    }
+  }
+
+  #[cfg(test)]
+  mod index {
+    use super::*;
+
+    #[test]
+    fn should_read_from_mapped_region_at_offset() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0001, &[0xAB]);
+      let mut uut = MemoryMap::new();
+      uut.map(0x2000..=0x2FFF, ram);
+
+      assert_eq!(uut[0x2001], 0xAB);
+    }
+
+    #[test]
+    fn should_return_last_bus_value_when_reading_unmapped_address() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0000, &[0x5A]);
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0x00FF, ram);
+
+      _ = uut[0x0000];
+
+      assert_eq!(uut[0x8000], 0x5A);
+    }
+
+    #[test]
+    fn should_return_last_written_value_when_reading_unmapped_address() {
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0x00FF, Generic64kMem::new());
+
+      uut[0x8000] = 0x33;
+
+      assert_eq!(uut[0x9000], 0x33);
+    }
+
+    #[test]
+    fn should_keep_undriven_bits_from_last_bus_value() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0000, &[0xA0]);
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0x00FF, ram);
+      uut.map(0xD000..=0xD000, LowNibbleDevice { value: 0x0C });
+
+      _ = uut[0x0000];
+
+      assert_eq!(uut[0xD000], 0xAC);
+    }
+
+    #[test]
+    fn should_prefer_region_mapped_later_when_ranges_overlap() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0010, &[0x01]);
+      let mut rom = Generic64kMem::new();
+      rom.insert(0x0000, &[0x02]);
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0xFFFF, ram);
+      uut.map(0x0010..=0x001F, rom);
+
+      assert_eq!(uut[0x0010], 0x02);
+    }
+  }
+
+  #[cfg(test)]
+  mod index_mut {
+    use super::*;
+
+    #[test]
+    fn should_write_to_mapped_region_at_offset() {
+      let mut uut = MemoryMap::new();
+      uut.map(0x2000..=0x2FFF, Generic64kMem::new());
+
+      uut[0x2002] = 0x7E;
+
+      assert_eq!(uut[0x2002], 0x7E);
+    }
+
+    #[test]
+    fn should_drop_writes_to_unmapped_addresses() {
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0x00FF, Generic64kMem::new());
+
+      uut[0x8000] = 0x7E;
+      _ = uut[0x0000];
+
+      assert_eq!(uut[0x8000], 0x00);
+    }
+
+    #[test]
+    fn should_compute_compound_assignment_from_region_contents() {
+      let mut ram = Generic64kMem::new();
+      ram.insert(0x0010, &[0x41]);
+      let mut uut = MemoryMap::new();
+      uut.map(0x0000..=0x00FF, ram);
+      uut.map(0xD000..=0xD000, LowNibbleDevice { value: 0x0C });
+      _ = uut[0xD000];
+
+      uut[0x0010] += 1;
+      uut.end_cycle();
+
+      assert_eq!(uut[0x0010], 0x42);
+    }
+
+    #[test]
+    fn should_deliver_latched_write_at_the_end_of_cycle() {
+      let mut uut = MemoryMap::new();
+      uut.map(0xD000..=0xD000, LowNibbleDevice { value: 0x00 });
+
+      uut[0xD000] = 0xF3;
+      uut.end_cycle();
+
+      assert_eq!(uut.bus_value(), 0xF3);
+      assert_eq!(uut[0xD000], 0xF3);
+    }
+
+    #[test]
+    fn should_report_faults_of_protected_region_at_bus_address() {
+      let mut rom = Generic64kMem::new();
+      rom.insert(0x0010, &[0x4C]);
+      rom.protect(0x0000..=0x1FFF, WriteProtection::Fault);
+      let mut uut = MemoryMap::new();
+      uut.map(0xE000..=0xFFFF, rom);
+
+      uut[0xE010] = 0x00;
+
+      assert_eq!(uut[0xE010], 0x4C);
+      assert_eq!(uut.take_faults(), vec![MemoryFault::ProtectedWrite(0xE010)]);
+    }
+  }
+}
@@ -0,0 +1,62 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+  consts::{Byte, Word},
+  memory::map::Mappable,
+};
+
+/// RAM mapped into the `MemoryMap`s of several CPUs, e.g. dual-port RAM between a computer and
+/// its drive. Clones share their contents, so each map gets a clone of the same RAM.
+#[derive(Clone)]
+pub struct SharedRam {
+  data: Rc<RefCell<Vec<Byte>>>,
+}
+
+impl SharedRam {
+  pub fn new(size: usize) -> Self {
+    SharedRam {
+      data: Rc::new(RefCell::new(vec![0; size])),
+    }
+  }
+
+  pub fn get(&self, offset: Word) -> Byte {
+    self.data.borrow()[offset as usize]
+  }
+
+  pub fn set(&self, offset: Word, value: Byte) {
+    self.data.borrow_mut()[offset as usize] = value;
+  }
+}
+
+impl Mappable for SharedRam {
+  fn read(&mut self, offset: Word) -> Byte {
+    self.get(offset)
+  }
+
+  fn write(&mut self, offset: Word, value: Byte) {
+    self.set(offset, value);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod mappable {
+    use crate::memory::{Memory, map::MemoryMap, shared_ram::SharedRam};
+
+    #[test]
+    fn should_share_contents_between_maps() {
+      let ram = SharedRam::new(0x100);
+      let mut first = MemoryMap::new();
+      first.map(0x1000..=0x10FF, ram.clone());
+      let mut second = MemoryMap::new();
+      second.map(0x8000..=0x80FF, ram.clone());
+
+      first[0x1010] = 0x42;
+      first.end_cycle();
+
+      assert_eq!(second[0x8010], 0x42);
+      assert_eq!(ram.get(0x10), 0x42);
+    }
+  }
+}
@@ -0,0 +1,239 @@
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::{
+  consts::{Byte, Word},
+  cpu::debugger::Symbols,
+  memory::Memory,
+};
+
+/// Copy of a memory range, taken with `Memory::peek` so capturing has no side effects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+  start: Word,
+  data: Vec<Byte>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+  pub addr: Word,
+  pub old: Byte,
+  pub new: Byte,
+}
+
+/// Consecutive changed addresses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedRange {
+  pub range: RangeInclusive<Word>,
+  pub old: Vec<Byte>,
+  pub new: Vec<Byte>,
+  pub symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryDiff {
+  pub ranges: Vec<ChangedRange>,
+}
+
+impl Snapshot {
+  pub fn capture(memory: &dyn Memory, range: RangeInclusive<Word>) -> Self {
+    Snapshot {
+      start: *range.start(),
+      data: range.map(|addr| memory.peek(addr)).collect(),
+    }
+  }
+
+  pub fn range(&self) -> RangeInclusive<Word> {
+    let len = self.data.len().saturating_sub(1) as Word;
+    self.start..=self.start.wrapping_add(len)
+  }
+
+  /// Compares the snapshot with the current content of the same range of `memory`.
+  pub fn diff(&self, memory: &dyn Memory) -> MemoryDiff {
+    self.diff_with(&Snapshot::capture(memory, self.range()))
+  }
+
+  /// Compares the snapshot with a later one of the same range.
+  pub fn diff_with(&self, later: &Snapshot) -> MemoryDiff {
+    let mut diff = MemoryDiff::default();
+    for (idx, (old, new)) in self.data.iter().zip(later.data.iter()).enumerate() {
+      if old != new {
+        diff.push(Change {
+          addr: self.start.wrapping_add(idx as Word),
+          old: *old,
+          new: *new,
+        });
+      }
+    }
+
+    diff
+  }
+}
+
+impl MemoryDiff {
+  pub fn is_empty(&self) -> bool {
+    self.ranges.is_empty()
+  }
+
+  pub fn changes(&self) -> impl Iterator<Item = Change> + '_ {
+    self.ranges.iter().flat_map(|changed| {
+      changed
+        .range
+        .clone()
+        .zip(changed.old.iter().zip(changed.new.iter()))
+        .map(|(addr, (old, new))| Change {
+          addr,
+          old: *old,
+          new: *new,
+        })
+    })
+  }
+
+  /// Names each changed range after the symbol at its first address.
+  pub fn annotate<S: Symbols>(mut self, symbols: &S) -> Self {
+    for changed in self.ranges.iter_mut() {
+      changed.symbol = symbols.get(changed.range.start());
+    }
+    self
+  }
+
+  fn push(&mut self, change: Change) {
+    if let Some(last) = self.ranges.last_mut()
+      && last.range.end().checked_add(1) == Some(change.addr)
+    {
+      last.range = *last.range.start()..=change.addr;
+      last.old.push(change.old);
+      last.new.push(change.new);
+      return;
+    }
+
+    self.ranges.push(ChangedRange {
+      range: change.addr..=change.addr,
+      old: vec![change.old],
+      new: vec![change.new],
+      symbol: None,
+    });
+  }
+}
+
+impl Display for ChangedRange {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let bytes = |values: &[Byte]| {
+      values
+        .iter()
+        .map(|value| format!("{value:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+    };
+
+    if self.range.start() == self.range.end() {
+      write!(f, "{:#06X}", self.range.start())?;
+    } else {
+      write!(f, "{:#06X}-{:#06X}", self.range.start(), self.range.end())?;
+    }
+    if let Some(symbol) = &self.symbol {
+      write!(f, " ({symbol})")?;
+    }
+    write!(f, ": {} -> {}", bytes(&self.old), bytes(&self.new))
+  }
+}
+
+impl Display for MemoryDiff {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.is_empty() {
+      return write!(f, "no changes");
+    }
+
+    for (idx, changed) in self.ranges.iter().enumerate() {
+      if idx > 0 {
+        writeln!(f)?;
+      }
+      write!(f, "{changed}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod diff {
+    use crate::{
+      cpu::debugger::symbol_table::SymbolTable,
+      memory::{
+        Generic64kMem,
+        snapshot::{Change, Snapshot},
+      },
+    };
+
+    #[test]
+    fn should_coalesce_consecutive_changes_into_ranges() {
+      let mut memory = Generic64kMem::new();
+      let snapshot = Snapshot::capture(&memory, 0x0200..=0x02FF);
+
+      memory.insert(0x0200, &[0x01, 0x02]);
+      memory.insert(0x0210, &[0x03]);
+      let diff = snapshot.diff(&memory);
+
+      assert_eq!(diff.ranges.len(), 2);
+      assert_eq!(diff.ranges[0].range, 0x0200..=0x0201);
+      assert_eq!(diff.ranges[0].old, vec![0x00, 0x00]);
+      assert_eq!(diff.ranges[0].new, vec![0x01, 0x02]);
+      assert_eq!(diff.ranges[1].range, 0x0210..=0x0210);
+      assert_eq!(
+        diff.changes().collect::<Vec<_>>(),
+        vec![
+          Change {
+            addr: 0x0200,
+            old: 0x00,
+            new: 0x01
+          },
+          Change {
+            addr: 0x0201,
+            old: 0x00,
+            new: 0x02
+          },
+          Change {
+            addr: 0x0210,
+            old: 0x00,
+            new: 0x03
+          },
+        ]
+      );
+    }
+
+    #[test]
+    fn should_ignore_changes_outside_of_captured_range() {
+      let mut memory = Generic64kMem::new();
+      let snapshot = Snapshot::capture(&memory, 0x0200..=0x02FF);
+
+      memory.insert(0x0300, &[0x01]);
+
+      assert!(snapshot.diff(&memory).is_empty());
+    }
+
+    #[test]
+    fn should_render_annotated_diff() {
+      let mut memory = Generic64kMem::new();
+      let snapshot = Snapshot::capture(&memory, 0x0000..=0xFFFF);
+      let mut symbols = SymbolTable::new();
+      symbols.insert(0x0200, "counter");
+
+      memory.insert(0x0200, &[0x01, 0x02]);
+      memory.insert(0xFFFF, &[0xAA]);
+      let diff = snapshot.diff(&memory).annotate(&symbols);
+
+      assert_eq!(
+        diff.to_string(),
+        "0x0200-0x0201 (counter): 00 00 -> 01 02\n0xFFFF: 00 -> AA"
+      );
+    }
+
+    #[test]
+    fn should_render_empty_diff() {
+      let memory = Generic64kMem::new();
+      let snapshot = Snapshot::capture(&memory, 0x0200..=0x02FF);
+
+      assert_eq!(snapshot.diff(&memory).to_string(), "no changes");
+    }
+  }
+}
@@ -0,0 +1,159 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use crate::{
+  consts::{Byte, Word},
+  memory::map::Mappable,
+};
+
+/// Writing a byte ends the program with it as exit code.
+pub const EXIT: Word = 0x00;
+/// Writing a byte appends it to the output.
+pub const CHAR_OUT: Word = 0x01;
+/// Reading returns the next input byte, or 0 once the input is exhausted.
+pub const CHAR_IN: Word = 0x02;
+
+#[derive(Default)]
+struct State {
+  exit_code: Option<Byte>,
+  output: String,
+  input: VecDeque<Byte>,
+}
+
+/// Device letting test programs end with an exit code and talk to the host, mapped into a
+/// `MemoryMap` with registers at `EXIT`, `CHAR_OUT` and `CHAR_IN` offsets. Clones share their
+/// state, so the host keeps a clone of the mapped device to inspect it.
+#[derive(Clone, Default)]
+pub struct TestDevice {
+  state: Rc<RefCell<State>>,
+}
+
+impl TestDevice {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn exit_code(&self) -> Option<Byte> {
+    self.state.borrow().exit_code
+  }
+
+  pub fn output(&self) -> String {
+    self.state.borrow().output.clone()
+  }
+
+  pub fn push_input(&self, input: &[Byte]) {
+    self.state.borrow_mut().input.extend(input);
+  }
+}
+
+impl Mappable for TestDevice {
+  fn read(&mut self, offset: Word) -> Byte {
+    match offset {
+      CHAR_IN => self.state.borrow_mut().input.pop_front().unwrap_or(0),
+      _ => 0,
+    }
+  }
+
+  fn write(&mut self, offset: Word, value: Byte) {
+    let mut state = self.state.borrow_mut();
+    match offset {
+      EXIT => state.exit_code = Some(value),
+      CHAR_OUT => state.output.push(value as char),
+      _ => {}
+    }
+  }
+
+  fn peek(&mut self, offset: Word) -> Byte {
+    match offset {
+      CHAR_IN => self.state.borrow().input.front().copied().unwrap_or(0),
+      _ => 0,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod mappable {
+    use crate::memory::{Memory, map::MemoryMap, test_device::TestDevice};
+
+    #[test]
+    fn should_collect_output_and_exit_code() {
+      let device = TestDevice::new();
+      let mut memory = MemoryMap::new();
+      memory.map(0xF000..=0xF002, device.clone());
+
+      memory[0xF001] = b'o';
+      memory[0xF001] = b'k';
+      memory[0xF000] = 0x03;
+      memory.end_cycle();
+
+      assert_eq!(device.output(), "ok");
+      assert_eq!(device.exit_code(), Some(0x03));
+    }
+
+    #[test]
+    fn should_supply_input_bytes_and_zero_when_exhausted() {
+      let device = TestDevice::new();
+      let mut memory = MemoryMap::new();
+      memory.map(0xF000..=0xF002, device.clone());
+      device.push_input(b"y");
+
+      assert_eq!(memory.peek(0xF002), b'y');
+      assert_eq!(memory[0xF002], b'y');
+      assert_eq!(memory[0xF002], 0x00);
+    }
+  }
+
+  #[cfg(test)]
+  mod execute_until_exit {
+    use crate::{
+      cpu::{CPU, utils::execute_until_exit},
+      memory::{Generic64kMem, map::MemoryMap, test_device::TestDevice},
+    };
+
+    #[test]
+    fn should_run_until_program_writes_exit_code() {
+      let device = TestDevice::new();
+      let mut ram = Generic64kMem::new();
+      // LDA #'h', STA $F001, LDA #$02, STA $F000, JMP $0600
+      ram.insert(
+        0x0600,
+        &[
+          0xA9, b'h', 0x8D, 0x01, 0xF0, 0xA9, 0x02, 0x8D, 0x00, 0xF0, 0x4C, 0x00, 0x06,
+        ],
+      );
+      ram.insert(0xFFFC, &[0x00, 0x06]);
+      let mut memory = MemoryMap::new();
+      memory.map(0x0000..=0xFFFF, ram);
+      memory.map(0xF000..=0xF002, device.clone());
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&memory);
+
+      let exit_code = execute_until_exit(&mut cpu, &mut memory, None, &device, 1000);
+
+      assert_eq!(exit_code, Some(0x02));
+      assert_eq!(device.output(), "h");
+    }
+
+    #[test]
+    fn should_give_up_when_cycle_limit_is_reached() {
+      let device = TestDevice::new();
+      let mut ram = Generic64kMem::new();
+      // loop: JMP $0600
+      ram.insert(0x0600, &[0x4C, 0x00, 0x06]);
+      ram.insert(0xFFFC, &[0x00, 0x06]);
+      let mut memory = MemoryMap::new();
+      memory.map(0x0000..=0xFFFF, ram);
+      memory.map(0xF000..=0xF002, device.clone());
+      let mut cpu = CPU::new_nmos();
+      cpu.reset(&memory);
+      let start = cpu.state().cycle;
+
+      let exit_code = execute_until_exit(&mut cpu, &mut memory, None, &device, 100);
+
+      assert_eq!(exit_code, None);
+      // stops at the first instruction boundary past the limit
+      assert_eq!(cpu.state().cycle - start, 102);
+    }
+  }
+}
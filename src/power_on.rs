@@ -0,0 +1,166 @@
+use crate::{consts::Byte, cpu::CPU, memory::Generic64kMem};
+
+/// SplitMix64 generator. Small and fast, and the same seed always yields the same sequence.
+#[derive(Debug, Clone)]
+pub struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Rng { state: seed }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  pub fn next_byte(&mut self) -> Byte {
+    (self.next_u64() >> 56) as Byte
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RamPattern {
+  Fill(Byte),
+  Random,
+  /// Alternating runs of `width` bytes of `first` and `second`, e.g. the $00/$FF stripes
+  /// some DRAMs power on with.
+  Stripes {
+    width: usize,
+    first: Byte,
+    second: Byte,
+  },
+}
+
+impl RamPattern {
+  pub fn fill(&self, ram: &mut [Byte], rng: &mut Rng) {
+    match self {
+      RamPattern::Fill(value) => ram.fill(*value),
+      RamPattern::Random => ram.iter_mut().for_each(|byte| *byte = rng.next_byte()),
+      RamPattern::Stripes {
+        width,
+        first,
+        second,
+      } => {
+        for (idx, stripe) in ram.chunks_mut((*width).max(1)).enumerate() {
+          stripe.fill(if idx % 2 == 0 { *first } else { *second });
+        }
+      }
+    }
+  }
+}
+
+/// Power-on state of a CPU and its RAM. Everything is derived from the seed, so a failing run
+/// can be reproduced by reusing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerOn {
+  pub seed: u64,
+  pub ram: RamPattern,
+  pub randomize_registers: bool,
+}
+
+impl PowerOn {
+  pub fn new(seed: u64) -> Self {
+    PowerOn {
+      seed,
+      ram: RamPattern::Random,
+      randomize_registers: true,
+    }
+  }
+
+  pub fn with_ram(mut self, ram: RamPattern) -> Self {
+    self.ram = ram;
+    self
+  }
+
+  pub fn with_randomized_registers(mut self, randomize_registers: bool) -> Self {
+    self.randomize_registers = randomize_registers;
+    self
+  }
+
+  /// Overwrites registers and RAM contents. Bytes set here are not considered initialized by
+  /// `Generic64kMem::track_uninitialized`.
+  pub fn apply(&self, cpu: &mut CPU, memory: &mut Generic64kMem) {
+    let mut rng = Rng::new(self.seed);
+    if self.randomize_registers {
+      cpu.randomize_registers(&mut rng);
+    }
+    self.ram.fill(&mut memory.data, &mut rng);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(test)]
+  mod apply {
+    use crate::{
+      cpu::CPU,
+      memory::Generic64kMem,
+      power_on::{PowerOn, RamPattern},
+    };
+
+    #[test]
+    fn should_produce_same_state_for_same_seed() {
+      let power_on = PowerOn::new(0xC0FFEE);
+      let (mut first_cpu, mut first_memory) = (CPU::new_nmos(), Generic64kMem::new());
+      let (mut second_cpu, mut second_memory) = (CPU::new_nmos(), Generic64kMem::new());
+
+      power_on.apply(&mut first_cpu, &mut first_memory);
+      power_on.apply(&mut second_cpu, &mut second_memory);
+
+      assert_eq!(first_memory.data, second_memory.data);
+      assert_eq!(
+        first_cpu.get_processor_status(),
+        second_cpu.get_processor_status()
+      );
+    }
+
+    #[test]
+    fn should_produce_different_ram_for_different_seeds() {
+      let (mut first_cpu, mut first_memory) = (CPU::new_nmos(), Generic64kMem::new());
+      let (mut second_cpu, mut second_memory) = (CPU::new_nmos(), Generic64kMem::new());
+
+      PowerOn::new(1).apply(&mut first_cpu, &mut first_memory);
+      PowerOn::new(2).apply(&mut second_cpu, &mut second_memory);
+
+      assert_ne!(first_memory.data, second_memory.data);
+    }
+
+    #[test]
+    fn should_fill_ram_with_stripes() {
+      let mut cpu = CPU::new_nmos();
+      let mut memory = Generic64kMem::new();
+
+      PowerOn::new(0)
+        .with_ram(RamPattern::Stripes {
+          width: 4,
+          first: 0x00,
+          second: 0xFF,
+        })
+        .apply(&mut cpu, &mut memory);
+
+      assert_eq!(
+        &memory[0x0000..0x000A],
+        &[0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00]
+      );
+      assert_eq!(memory[0xFFFF], 0xFF);
+    }
+
+    #[test]
+    fn should_keep_break_flag_clear_and_unused_flag_set() {
+      for seed in 0..64 {
+        let mut cpu = CPU::new_nmos();
+        let mut memory = Generic64kMem::new();
+
+        PowerOn::new(seed).apply(&mut cpu, &mut memory);
+
+        assert_eq!(cpu.get_processor_status() & 0b0011_0000, 0b0010_0000);
+      }
+    }
+  }
+}
@@ -0,0 +1,339 @@
+use std::{cell::RefCell, ops::RangeInclusive, rc::Rc};
+
+use crate::{
+  consts::{Byte, Word},
+  cpu::{CPU, RunResult, StopReason, scheduler::ClockRatio},
+  memory::{
+    MemoryFault,
+    map::{Mappable, MemoryMap},
+  },
+};
+
+/// Peripheral clocked by a `System`. Bus accesses go through the `Mappable` methods, with
+/// addresses as offsets from the start of the range the device is mapped at.
+pub trait Device: Mappable {
+  /// Advances the device by one of its own clock cycles.
+  fn tick(&mut self);
+
+  /// Level of the device's IRQ output.
+  fn irq(&self) -> bool {
+    false
+  }
+
+  /// Level of the device's NMI output.
+  fn nmi(&self) -> bool {
+    false
+  }
+}
+
+// Lets the memory map reach a device the system ticks too.
+struct DeviceBus<D>(Rc<RefCell<D>>);
+
+impl<D: Device> Mappable for DeviceBus<D> {
+  fn read(&mut self, offset: Word) -> Byte {
+    self.0.borrow_mut().read(offset)
+  }
+
+  fn write(&mut self, offset: Word, value: Byte) {
+    self.0.borrow_mut().write(offset, value);
+  }
+
+  fn driven_bits(&self, offset: Word) -> Byte {
+    self.0.borrow().driven_bits(offset)
+  }
+
+  fn take_faults(&self) -> Vec<MemoryFault> {
+    self.0.borrow().take_faults()
+  }
+
+  fn dummy_read(&mut self, offset: Word) -> Byte {
+    self.0.borrow_mut().dummy_read(offset)
+  }
+
+  fn peek(&mut self, offset: Word) -> Byte {
+    self.0.borrow_mut().peek(offset)
+  }
+
+  fn poke(&mut self, offset: Word, value: Byte) {
+    self.0.borrow_mut().poke(offset, value);
+  }
+}
+
+struct ClockedDevice {
+  device: Rc<RefCell<dyn Device>>,
+  ratio: ClockRatio,
+  // device cycles owed, in units of 1 / ratio.per_master_cycles
+  accumulated: u64,
+}
+
+/// A CPU with its memory map and devices, advanced together one CPU cycle at a time.
+///
+/// Each cycle the CPU ticks first, then the devices in the order they were added, running as
+/// many of their cycles as their `ClockRatio` to the CPU clock gives. The interrupt lines of
+/// the CPU are then set to the wired-OR of the device outputs.
+pub struct System {
+  cpu: CPU,
+  memory: MemoryMap,
+  devices: Vec<ClockedDevice>,
+}
+
+impl System {
+  pub fn new(cpu: CPU, memory: MemoryMap) -> Self {
+    System {
+      cpu,
+      memory,
+      devices: Vec::new(),
+    }
+  }
+
+  /// Maps `device` at `range` and clocks it at `ratio` of the CPU clock. The returned handle
+  /// gives access to the device from the host.
+  pub fn add_device<D: Device + 'static>(
+    &mut self,
+    range: RangeInclusive<Word>,
+    device: D,
+    ratio: ClockRatio,
+  ) -> Rc<RefCell<D>> {
+    let device = Rc::new(RefCell::new(device));
+    self.memory.map(range, DeviceBus(device.clone()));
+    self.devices.push(ClockedDevice {
+      device: device.clone(),
+      ratio,
+      accumulated: 0,
+    });
+    device
+  }
+
+  pub fn cpu(&self) -> &CPU {
+    &self.cpu
+  }
+
+  pub fn cpu_mut(&mut self) -> &mut CPU {
+    &mut self.cpu
+  }
+
+  pub fn memory(&self) -> &MemoryMap {
+    &self.memory
+  }
+
+  pub fn memory_mut(&mut self) -> &mut MemoryMap {
+    &mut self.memory
+  }
+
+  /// Advances the system by one CPU cycle.
+  pub fn tick(&mut self) {
+    self.cpu.tick(&mut self.memory);
+
+    let mut irq = false;
+    let mut nmi = false;
+    for clocked in self.devices.iter_mut() {
+      let mut device = clocked.device.borrow_mut();
+      clocked.accumulated += clocked.ratio.cycles as u64;
+      while clocked.accumulated >= clocked.ratio.per_master_cycles as u64 {
+        clocked.accumulated -= clocked.ratio.per_master_cycles as u64;
+        device.tick();
+      }
+
+      irq |= device.irq();
+      nmi |= device.nmi();
+    }
+
+    self.cpu.set_irq(irq);
+    self.cpu.set_nmi(nmi);
+  }
+
+  /// Executes one whole instruction, or the rest of the current one when called mid-instruction.
+  pub fn step(&mut self) -> RunResult {
+    let start = self.cpu.state().cycle;
+    self.tick();
+    while !self.cpu.instruction_done() {
+      self.tick();
+    }
+
+    RunResult {
+      cycles: self.cpu.state().cycle - start,
+      stop_reason: StopReason::InstructionDone,
+    }
+  }
+
+  pub fn run_for_cycles(&mut self, cycles: usize) -> RunResult {
+    let start = self.cpu.state().cycle;
+    for _ in 0..cycles {
+      self.tick();
+    }
+
+    RunResult {
+      cycles: self.cpu.state().cycle - start,
+      stop_reason: StopReason::CycleBudget,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    consts::{Byte, Word},
+    cpu::{CPU, scheduler::ClockRatio},
+    memory::{
+      Generic64kMem, Memory,
+      map::{Mappable, MemoryMap},
+    },
+    system::{Device, System},
+  };
+
+  // Counts down on every tick and holds its interrupt output once it reaches zero, until the
+  // counter is written again.
+  struct Timer {
+    counter: Byte,
+    ticks: usize,
+    nmi: bool,
+  }
+
+  impl Timer {
+    fn new(counter: Byte, nmi: bool) -> Self {
+      Timer {
+        counter,
+        ticks: 0,
+        nmi,
+      }
+    }
+  }
+
+  impl Mappable for Timer {
+    fn read(&mut self, _offset: Word) -> Byte {
+      self.counter
+    }
+
+    fn write(&mut self, _offset: Word, value: Byte) {
+      self.counter = value;
+    }
+  }
+
+  impl Device for Timer {
+    fn tick(&mut self) {
+      self.ticks += 1;
+      self.counter = self.counter.saturating_sub(1);
+    }
+
+    fn irq(&self) -> bool {
+      !self.nmi && self.counter == 0
+    }
+
+    fn nmi(&self) -> bool {
+      self.nmi && self.counter == 0
+    }
+  }
+
+  fn system(program: &[Byte]) -> System {
+    let mut ram = Generic64kMem::new();
+    ram.insert(0x0200, program);
+    // handlers: NMI at $0300, IRQ at $0400, both INX, loop forever
+    ram.insert(0x0300, &[0xE8, 0x4C, 0x01, 0x03]);
+    ram.insert(0x0400, &[0xE8, 0x4C, 0x01, 0x04]);
+    ram.insert(0xFFFA, &[0x00, 0x03, 0x00, 0x02, 0x00, 0x04]);
+    let mut memory = MemoryMap::new();
+    memory.map(0x0000..=0xFFFF, ram);
+
+    let mut cpu = CPU::new_nmos();
+    cpu.reset(&memory);
+    let mut state = cpu.state();
+    state.sp = 0xFF;
+    cpu.set_state(state);
+    System::new(cpu, memory)
+  }
+
+  #[cfg(test)]
+  mod tick {
+    use super::*;
+
+    #[test]
+    fn should_clock_devices_at_their_ratio() {
+      // loop: JMP $0200
+      let mut uut = system(&[0x4C, 0x00, 0x02]);
+      let every_cycle = uut.add_device(
+        0xD000..=0xD000,
+        Timer::new(0xFF, false),
+        ClockRatio::default(),
+      );
+      let doubled = uut.add_device(
+        0xD001..=0xD001,
+        Timer::new(0xFF, false),
+        ClockRatio::new(2, 1),
+      );
+      let divided = uut.add_device(
+        0xD002..=0xD002,
+        Timer::new(0xFF, false),
+        ClockRatio::new(1, 4),
+      );
+
+      uut.run_for_cycles(10);
+
+      assert_eq!(every_cycle.borrow().ticks, 10);
+      assert_eq!(doubled.borrow().ticks, 20);
+      assert_eq!(divided.borrow().ticks, 2);
+    }
+
+    #[test]
+    fn should_give_cpu_access_to_device_registers() {
+      // LDA #$20, STA $D000, LDX $D000
+      let mut uut = system(&[0xA9, 0x20, 0x8D, 0x00, 0xD0, 0xAE, 0x00, 0xD0]);
+      let timer = uut.add_device(
+        0xD000..=0xD000,
+        Timer::new(0xFF, false),
+        ClockRatio::new(0, 1),
+      );
+
+      uut.step();
+      uut.step();
+      uut.step();
+
+      assert_eq!(timer.borrow().counter, 0x20);
+      assert_eq!(uut.cpu().state().x, 0x20);
+    }
+
+    #[test]
+    fn should_take_irq_of_device_when_enabled() {
+      // CLI, loop: JMP $0201
+      let mut uut = system(&[0x58, 0x4C, 0x01, 0x02]);
+      uut.add_device(0xD000..=0xD000, Timer::new(6, false), ClockRatio::default());
+
+      uut.run_for_cycles(20);
+
+      let state = uut.cpu().state();
+      assert_eq!(state.pc & 0xFF00, 0x0400);
+      assert_eq!(state.x, 1);
+      // status pushed with break clear, return address at the interrupted JMP
+      assert_eq!(uut.memory().peek(0x01FD) & 0b0001_0000, 0);
+      assert_eq!(uut.memory().peek(0x01FE), 0x01);
+      assert_eq!(uut.memory().peek(0x01FF), 0x02);
+    }
+
+    #[test]
+    fn should_ignore_irq_when_disabled() {
+      // loop: JMP $0200
+      let mut uut = system(&[0x4C, 0x00, 0x02]);
+      uut.add_device(0xD000..=0xD000, Timer::new(1, false), ClockRatio::default());
+
+      uut.run_for_cycles(20);
+
+      assert_eq!(uut.cpu().state().pc & 0xFF00, 0x0200);
+    }
+
+    #[test]
+    fn should_take_nmi_once_per_assertion() {
+      // loop: JMP $0200
+      let mut uut = system(&[0x4C, 0x00, 0x02]);
+      let timer = uut.add_device(0xD000..=0xD000, Timer::new(4, true), ClockRatio::default());
+
+      uut.run_for_cycles(40);
+
+      assert_eq!(uut.cpu().state().pc & 0xFF00, 0x0300);
+      assert_eq!(uut.cpu().state().x, 1);
+
+      timer.borrow_mut().counter = 2;
+      uut.run_for_cycles(20);
+
+      assert_eq!(uut.cpu().state().x, 2);
+    }
+  }
+}
@@ -69,6 +69,7 @@ fn should_change_word_to_lower_case() {
 
   assert_eq!(str::from_utf8(&memory[0x0500..0x050C]), Ok("some message"));
   assert_eq!(cpu.get_processor_status() & 0b00000001, 0);
+  assert_eq!(cpu.state().y, 0x0C);
 }
 
 #[test]